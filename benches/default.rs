@@ -4,7 +4,9 @@ mod batch;
 mod event;
 mod files;
 mod http;
+mod lookup;
 mod lua;
+mod metric_to_log;
 mod metrics_snapshot;
 mod regex;
 mod template;
@@ -15,7 +17,9 @@ criterion_main!(
     event::benches,
     files::benches,
     http::benches,
+    lookup::benches,
     lua::benches,
+    metric_to_log::benches,
     metrics_snapshot::benches,
     regex::benches,
     template::benches,
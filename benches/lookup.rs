@@ -0,0 +1,71 @@
+use criterion::{criterion_group, BatchSize, Criterion, Throughput};
+use lookup::{Lookup, LookupBuf};
+
+const SUFFICIENTLY_COMPLEX: &str =
+    r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[0].nested_lookup[0][0]"#;
+
+const SIMPLE: &[&str] = &[
+    "message",
+    "host",
+    "timestamp",
+    "kubernetes.pod_name",
+    "labels.app",
+    "nested.field.value",
+    "array[0]",
+    "array[1].field",
+];
+
+fn bench_lookup_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup/parse");
+
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("view/sufficiently_complex", |b| {
+        b.iter(|| Lookup::from_str(SUFFICIENTLY_COMPLEX).unwrap())
+    });
+    group.bench_function("buf/sufficiently_complex", |b| {
+        b.iter(|| SUFFICIENTLY_COMPLEX.parse::<LookupBuf>().unwrap())
+    });
+
+    group.throughput(Throughput::Elements(SIMPLE.len() as u64));
+    group.bench_function("view/simple", |b| {
+        b.iter(|| {
+            SIMPLE
+                .iter()
+                .map(|path| Lookup::from_str(path).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("buf/simple", |b| {
+        b.iter(|| {
+            SIMPLE
+                .iter()
+                .map(|path| path.parse::<LookupBuf>().unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_lookup_to_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup/to_string");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("buf/sufficiently_complex", |b| {
+        let lookup = SUFFICIENTLY_COMPLEX.parse::<LookupBuf>().unwrap();
+
+        b.iter(|| lookup.to_string())
+    });
+
+    group.bench_function("buf/round_trip", |b| {
+        b.iter_batched(
+            || SUFFICIENTLY_COMPLEX.parse::<LookupBuf>().unwrap(),
+            |lookup| lookup.to_string().parse::<LookupBuf>().unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lookup_parse, bench_lookup_to_string);
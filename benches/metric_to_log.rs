@@ -0,0 +1,90 @@
+use chrono::Utc;
+use criterion::{criterion_group, BatchSize, BenchmarkId, Criterion, Throughput};
+use vector::{
+    event::{
+        metric::{Bucket, MetricKind, MetricValue},
+        Event, Metric,
+    },
+    transforms::{
+        metric_to_log::{MetricToLog, MetricToLogConfig},
+        FunctionTransform,
+    },
+};
+
+fn metric(value: MetricValue) -> Event {
+    Metric::new("requests", MetricKind::Absolute, value)
+        .with_tags(Some(
+            vec![
+                ("host".to_owned(), "localhost".to_owned()),
+                ("code".to_owned(), "200".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+        .with_timestamp(Some(Utc::now()))
+        .into()
+}
+
+fn histogram(buckets: usize) -> Event {
+    metric(MetricValue::AggregatedHistogram {
+        buckets: (0..buckets)
+            .map(|i| Bucket {
+                upper_limit: 2f64.powi(i as i32),
+                count: i as u32,
+            })
+            .collect(),
+        count: buckets as u32,
+        sum: buckets as f64,
+    })
+}
+
+fn bench_metric_to_log(c: &mut Criterion) {
+    let transform = MetricToLog::new(&MetricToLogConfig {
+        host_tag: Some("host".into()),
+        ..Default::default()
+    });
+
+    let mut group = c.benchmark_group("metric_to_log");
+    group.throughput(Throughput::Elements(1));
+
+    let scalars = vec![
+        ("counter", metric(MetricValue::Counter { value: 1.0 })),
+        ("gauge", metric(MetricValue::Gauge { value: 1.0 })),
+    ];
+    for (name, event) in scalars {
+        group.bench_function(name, |b| {
+            let mut transform = transform.clone();
+            b.iter_batched(
+                || (Vec::with_capacity(1), event.clone()),
+                |(mut output, event)| {
+                    transform.transform(&mut output, event);
+                    output
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    for buckets in &[8, 64, 512] {
+        let event = histogram(*buckets);
+        group.bench_with_input(
+            BenchmarkId::new("aggregated_histogram", buckets),
+            &event,
+            |b, event| {
+                let mut transform = transform.clone();
+                b.iter_batched(
+                    || (Vec::with_capacity(1), event.clone()),
+                    |(mut output, event)| {
+                        transform.transform(&mut output, event);
+                        output
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_metric_to_log);
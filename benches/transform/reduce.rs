@@ -6,9 +6,13 @@ use criterion::{
     Throughput,
 };
 use indexmap::IndexMap;
+use indoc::indoc;
 use std::num::NonZeroUsize;
 use std::time::Duration;
-use vector::transforms::reduce::{Reduce, ReduceConfig};
+use vector::{
+    event::Event,
+    transforms::reduce::{Reduce, ReduceConfig},
+};
 use vector_core::transform::Transform;
 
 #[derive(Debug)]
@@ -33,23 +37,61 @@ fn reduce(c: &mut Criterion) {
         NonZeroUsize::new(128).unwrap(),
         NonZeroUsize::new(2).unwrap(),
     );
+    let group_by_message = ReduceConfig {
+        expire_after_ms: None,
+        flush_period_ms: None,
+        group_by: vec![String::from("message")],
+        merge_strategies: IndexMap::default(),
+        ends_when: None,
+        starts_when: None,
+        max_bytes_per_group: None,
+        ..Default::default()
+    };
     for param in &[
-        // The `Reduce` transform has a high configuration surface. For now we
-        // only benchmark the "proof of concept" configuration, demonstrating
-        // that the benchmark does minimally work. Once we have soak tests with
-        // reduces in them we should extend this array to include those
-        // configuratoins.
+        // The `Reduce` transform has a high configuration surface. The "proof
+        // of concept" configuration merges a stream into two groups by the
+        // "message" field, with the default merge strategies. Once we have
+        // soak tests with reduces in them we should extend this array to
+        // include those configurations.
         Param {
             slug: "proof_of_concept",
             input: fixed_stream.clone(),
-            reduce_config: ReduceConfig {
-                expire_after_ms: None,
-                flush_period_ms: None,
-                group_by: vec![String::from("message")],
-                merge_strategies: IndexMap::default(),
-                ends_when: None,
-                starts_when: None,
-            },
+            reduce_config: group_by_message.clone(),
+        },
+        // Modification of previous where every event opens its own group,
+        // exercising the creation of reduce states rather than merging.
+        Param {
+            slug: "group_by_distinct",
+            input: FixedLogStream::new(
+                NonZeroUsize::new(128).unwrap(),
+                NonZeroUsize::new(128).unwrap(),
+            ),
+            reduce_config: group_by_message.clone(),
+        },
+        // Measurement where a handful of groups merge numeric and string
+        // fields with explicit strategies.
+        Param {
+            slug: "merge_strategies",
+            input: FixedLogStream::new_from_vec(
+                (0..128_i64)
+                    .map(|i| {
+                        let mut event = Event::from(format!("event{}", i % 8));
+                        event.as_mut_log().insert("count", i);
+                        event.as_mut_log().insert("max", i);
+                        event.as_mut_log().insert("text", format!("text{}", i));
+                        event.as_mut_log().insert("list", i);
+                        event
+                    })
+                    .collect(),
+            ),
+            reduce_config: toml::from_str(indoc! {r#"
+                group_by = ["message"]
+                merge_strategies.count = "sum"
+                merge_strategies.max = "max"
+                merge_strategies.text = "concat"
+                merge_strategies.list = "array"
+            "#})
+            .unwrap(),
         },
     ] {
         group.throughput(Throughput::Elements(param.input.len() as u64));
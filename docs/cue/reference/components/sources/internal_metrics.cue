@@ -170,6 +170,12 @@ components: sources: internal_metrics: {
 			type:              "histogram"
 			default_namespace: "vector"
 		}
+		byte_limit_events_flushed_total: {
+			description:       "The number of combined events the reduce transform has flushed because they exceeded `max_bytes_per_group`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints."
 			type:              "counter"
@@ -672,6 +678,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		reduce_flush_ticks_total: {
+			description:       "The number of times the reduce transform has checked for stale events to flush."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		request_errors_total: {
 			description:       "The total number of requests errors for this component."
 			type:              "counter"
@@ -775,6 +787,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		stdin_lines_truncated_total: {
+			description:       "The total number of lines from stdin that were longer than `max_length` and got truncated."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		stdin_reads_failed_total: {
 			description:       "The total number of errors reading from stdin."
 			type:              "counter"
@@ -13,7 +13,8 @@ components: sources: stdin: {
 	}
 
 	features: {
-		multiline: enabled: false
+		multiline: enabled: true
+		encoding: enabled:  true
 		receive: {
 			from: {
 				service: services.stdin
@@ -45,6 +46,90 @@ components: sources: stdin: {
 	}
 
 	configuration: {
+		buffer_size: {
+			common:      false
+			description: "The number of lines the reader may queue ahead of the pipeline before it stops reading from STDIN."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 1024
+				unit:    "lines"
+			}
+		}
+		decoding: {
+			common:      false
+			description: "How each line is turned into an event."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "bytes"
+				enum: {
+					bytes: "The line is kept as-is in the `message` field."
+					json:  "The line is parsed as a JSON object whose top-level fields become fields of the event. Lines that are not valid JSON objects are kept as the `message` and counted in `decode_errors_total`."
+				}
+				syntax: "literal"
+			}
+		}
+		emit_eof_marker: {
+			common:      false
+			description: "Send one more event after the last line once STDIN is closed, with `eof_marker_key` set to `true`. The marker is not sent when the source stops for another reason, and it counts towards `max_events`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		eof_marker_key: {
+			common:        false
+			description:   "The key set to `true` on the EOF marker event."
+			relevant_when: "emit_eof_marker = `true`"
+			required:      false
+			warnings: []
+			type: string: {
+				default: "eof"
+				syntax:  "literal"
+			}
+		}
+		fields: {
+			common:      false
+			description: "A table of static key/value pairs added to every event, including the EOF marker. They are added last, so they replace any field the source set at the same key, such as `message`."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						environment: "production"
+						int_field:   1
+						parent: child_field: "child_value"
+					},
+				]
+				options: {
+					"*": {
+						description: "The name of the field to add. Accepts all supported configuration types. Use `.` for adding nested fields."
+						required:    true
+						warnings: []
+						type: "*": {}
+					}
+				}
+			}
+		}
+		framing: {
+			common:      false
+			description: "Configures how the incoming byte stream is split into lines."
+			required:    false
+			warnings: []
+			type: object: options: {
+				delimiter: {
+					common:      false
+					description: "The byte, given as an integer, that separates lines on STDIN. The delimiter is not included in the event. A trailing `\\r` is only dropped when the delimiter is the default `\\n`."
+					required:    false
+					warnings: []
+					type: uint: {
+						default: 10
+						examples: [0, 59]
+						unit: null
+					}
+				}
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -59,9 +144,37 @@ components: sources: stdin: {
 				syntax:  "literal"
 			}
 		}
+		line_number_key: {
+			category:    "Context"
+			common:      false
+			description: "When set, each event gets its line number, starting at 1, at this key. An event merged from several lines by `multiline` keeps the number of its first line."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["line"]
+				syntax: "literal"
+			}
+		}
+		max_events: {
+			common:      false
+			description: "Stop reading and shut the source down once this many events have been sent. By default the source reads until STDIN is closed."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [1, 1000]
+				unit: "events"
+			}
+		}
 		max_length: {
 			common:      false
-			description: "The maximum bytes size of a message before rest of it will be discarded."
+			description: """
+				The maximum bytes size of a line. Longer lines are truncated to this size, backing off to the last
+				complete UTF-8 character, and the rest of the line is discarded without being buffered. Each
+				truncation is logged and counted in `stdin_lines_truncated_total`. Truncation is on by default since
+				this limit used to be ignored; raise it if you pipe in lines longer than 100 KiB.
+				"""
 			required:    false
 			warnings: []
 			type: uint: {
@@ -69,10 +182,26 @@ components: sources: stdin: {
 				unit:    "bytes"
 			}
 		}
+		source_type_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name that records `stdin` as the source type of each event. Defaults to the
+				[global `source_type_key` option](\(urls.vector_configuration)/global-options#log_schema.source_type_key).
+				An empty string omits the field.
+				"""
+			required:    false
+			warnings: []
+			type: string: {
+				default: "source_type"
+				examples: ["source", ""]
+				syntax: "literal"
+			}
+		}
 	}
 
 	output: logs: line: {
-		description: "An individual event from STDIN."
+		description: "An individual event from STDIN. Lines can be merged using the `multiline` options."
 		fields: {
 			host:      fields._local_host
 			message:   fields._raw_line
@@ -104,15 +233,22 @@ components: sources: stdin: {
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
-				Each line is read until a new line delimiter, the `0xA` byte, is found.
+				Each line is read until a new line delimiter, the `0xA` byte, is found. A different delimiter byte can be
+				configured with the `framing.delimiter` option.
+
+				Lines are split on the raw bytes before any `encoding.charset` transcoding, so only charsets that encode
+				the delimiter as a single ASCII byte, such as UTF-8 or Windows-1252, split correctly. UTF-16 input
+				is not supported.
 				"""
 		}
 	}
 
 	telemetry: metrics: {
-		events_in_total:          components.sources.internal_metrics.output.metrics.events_in_total
-		processed_bytes_total:    components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:   components.sources.internal_metrics.output.metrics.processed_events_total
-		stdin_reads_failed_total: components.sources.internal_metrics.output.metrics.stdin_reads_failed_total
+		decode_errors_total:         components.sources.internal_metrics.output.metrics.decode_errors_total
+		events_in_total:             components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:       components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:      components.sources.internal_metrics.output.metrics.processed_events_total
+		stdin_lines_truncated_total: components.sources.internal_metrics.output.metrics.stdin_lines_truncated_total
+		stdin_reads_failed_total:    components.sources.internal_metrics.output.metrics.stdin_reads_failed_total
 	}
 }
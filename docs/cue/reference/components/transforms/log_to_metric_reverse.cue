@@ -0,0 +1,111 @@
+package metadata
+
+components: transforms: log_to_metric_reverse: {
+	title: "Log to Metric (Reverse)"
+
+	description: """
+		Converts a log event produced by the `metric_to_log` transform back into the
+		metric it was created from.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				Only the layout written by `metric_to_log` with its default settings is
+				understood. Logs whose value type can't be recognized are dropped.
+				""",
+		]
+	}
+
+	configuration: {
+		host_tag: {
+			common:      true
+			description: "Tag key the log's host field is moved back into. This should match the `host_tag` of the `metric_to_log` transform that produced the log."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "hostname"
+				examples: ["host", "hostname"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	output: metrics: {
+		counter:      output._passthrough_counter
+		distribution: output._passthrough_distribution
+		gauge:        output._passthrough_gauge
+		histogram:    output._passthrough_histogram
+		set:          output._passthrough_set
+		summary:      output._passthrough_summary
+	}
+
+	examples: [
+		{
+			title: "Log To Metric (Reverse)"
+			configuration: #"""
+				[transforms.log_to_metric_reverse]
+				type = "log_to_metric_reverse"
+				host_tag = "host"
+				"""#
+			input: log: {
+				name:      "requests"
+				timestamp: "2020-08-01T21:15:47+00:00"
+				host:      "my.host.com"
+				tags: {
+					"code": "200"
+				}
+				kind: "incremental"
+				counter: {
+					value: 1.0
+				}
+			}
+			output: [{metric: {
+				kind:      "incremental"
+				name:      "requests"
+				timestamp: "2020-08-01T21:15:47+00:00"
+				tags: {
+					host: "my.host.com"
+					code: "200"
+				}
+				counter: {
+					value: 1.0
+				}
+			}}]
+		},
+	]
+
+	how_it_works: {}
+
+	telemetry: metrics: {
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
@@ -36,6 +36,39 @@ components: transforms: metric_to_log: {
 	}
 
 	configuration: {
+		default_host: {
+			common:      false
+			description: "The host to set on logs of metrics that don't have the `host_tag` tag. When unset, such logs have no host field."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["my-host"]
+				syntax: "literal"
+			}
+		}
+		explode_histograms: {
+			common:      false
+			description: "Set to `true` to emit one log per bucket for aggregated histograms (with `le` and `count` fields) and one log per quantile for aggregated summaries (with `quantile` and `value` fields). Each log keeps the name, kind, timestamp, and tags of the metric."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		field_map: {
+			common:      false
+			description: "A map of field names to new names. Each listed field of the resulting log is renamed after the metric has been converted; fields that aren't listed keep their name. Both names are field paths, and an invalid path is rejected when the transform is built."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						timestamp: "@timestamp"
+						name:      "metric_name"
+					},
+				]
+				options: {}
+			}
+		}
 		host_tag: {
 			common:      true
 			description: "Tag key that identifies the source host."
@@ -47,7 +80,50 @@ components: transforms: metric_to_log: {
 				syntax: "literal"
 			}
 		}
-		timezone: configuration._timezone
+		prefix_with_name: {
+			common:      false
+			description: "Set to `true` to nest the metric's value under its name, for example `requests.counter.value` instead of `counter.value`. The `name`, `kind`, `timestamp`, and tag fields stay at the top level."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		timestamp_missing: {
+			common:      false
+			description: "Controls how metrics without a timestamp are handled."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "now"
+				enum: {
+					now:  "Use the time the metric was processed as the timestamp."
+					drop: "Drop the metric."
+					none: "Leave the timestamp field out of the log."
+				}
+				syntax: "literal"
+			}
+		}
+		timezone: {
+			common:      false
+			description: "Has no effect. Metric timestamps always carry their time zone, so there is nothing to convert."
+			required:    false
+			warnings: ["This option has been deprecated and is ignored; a warning is logged when it is set."]
+			type: string: {
+				default: null
+				examples: ["local", "America/NewYork", "EST5EDT"]
+				syntax: "literal"
+			}
+		}
+		type_key: {
+			common:      false
+			description: "If set, the name of the metric's value type (for example `counter` or `aggregated_histogram`) is inserted into the log at this path. An invalid path is rejected when the transform is built."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["metric_type"]
+				syntax: "literal"
+			}
+		}
 	}
 
 	input: {
@@ -113,9 +189,14 @@ components: transforms: metric_to_log: {
 		},
 	]
 
-	how_it_works: {}
-
-	telemetry: metrics: {
-		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	how_it_works: {
+		telemetry: {
+			title: "Telemetry"
+			body: """
+				Every metric can be represented as a log, so this transform has no conversion errors to report.
+				Earlier versions serialized each metric to JSON first and counted failures in
+				`processing_errors_total`; that step, and the counter, are gone.
+				"""
+		}
 	}
 }
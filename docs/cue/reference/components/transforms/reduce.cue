@@ -36,6 +36,35 @@ components: transforms: reduce: {
 	}
 
 	configuration: {
+		default_merge_strategy: {
+			common:      false
+			description: "The merge strategy to use for every field that isn't listed in `merge_strategies`. Fields that can't be merged with this strategy are dropped, as they would be if they were listed. When unset, the default behavior described under `merge_strategies` applies."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				enum: {
+					arg_max:        "The value from the event with the greatest value of another field, such as the error at peak latency. Set as `{ strategy = \"arg_max\", by = \"latency\" }`."
+					arg_min:        "The value from the event with the least value of another field. Set as `{ strategy = \"arg_min\", by = \"latency\" }`."
+					array:          "Each value is appended to an array."
+					concat:         "Concatenate each string value (delimited with a space)."
+					concat_newline: "Concatenate each string value (delimited with a newline)."
+					discard:        "Discard all but the first value found."
+					drop:           "Drop the field from the reduced event entirely."
+					sum:            "Sum all numeric values."
+					max:            "The maximum of all numeric values, or the lexicographically greatest string if the first value is a string. Numbers and strings can't be compared, so values of the other kind are dropped."
+					min:            "The minimum of all numeric values, or the lexicographically least string if the first value is a string. Numbers and strings can't be compared, so values of the other kind are dropped."
+				}
+				syntax: "literal"
+			}
+		}
+		drop_group_by_fields: {
+			common:      false
+			description: "Whether the fields listed in `group_by` are removed from each combined event. Events are still grouped by their values."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		ends_when: {
 			common: false
 			description: """
@@ -52,6 +81,70 @@ components: transforms: reduce: {
 				syntax: "literal"
 			}
 		}
+		ends_when_by: {
+			common: false
+			description: """
+				A map of values of `ends_when_by_field` to conditions used to distinguish the final event of a
+				transaction, as an alternative to `ends_when`. The condition for an event is the one for its
+				value of `ends_when_by_field`. Events without a matching condition don't end their transaction.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						error: "exists(.retries_exhausted)"
+						ok:    "exists(.done)"
+					},
+				]
+				options: {
+					"*": {
+						description: "The condition for events with this value of `ends_when_by_field`."
+						required:    true
+						warnings: []
+						type: string: {
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+		ends_when_by_field: {
+			common:      false
+			description: "The field whose value selects the condition from `ends_when_by`. Required when `ends_when_by` is set."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["status"]
+				syntax: "literal"
+			}
+		}
+		ends_when_field_changes: {
+			common:      false
+			description: "A field whose value is expected to stay the same within a transaction. When an event's value of this field differs from that of the current transaction, the transaction is flushed and a new one is started with the event. A missing field counts as a value of its own."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["status"]
+				syntax: "literal"
+			}
+		}
+		ends_when_on: {
+			common:      false
+			description: "What the `ends_when` condition is checked against."
+			required:    false
+			warnings: ["With `state`, the combined event is rebuilt from every merged field for each incoming event, so the cost of each event grows with the size of its transaction. Prefer `event` for transactions that merge large arrays or strings."]
+			type: string: {
+				default: "event"
+				enum: {
+					event: "Check each incoming event before it is combined."
+					state: "Check the combined event of the transaction after the incoming event was added, so that a transaction can end on an accumulated value."
+				}
+				syntax: "literal"
+			}
+		}
 		expire_after_ms: {
 			common:      false
 			description: "A maximum period of time to wait after the last event is received before a combined event should be considered complete."
@@ -74,7 +167,7 @@ components: transforms: reduce: {
 		}
 		group_by: {
 			common:      true
-			description: "An ordered list of fields by which to group events. Each group is combined independently, allowing you to keep independent events separate. When no fields are specified, all events will be combined in a single group. Events missing a specified field will be combined in their own group."
+			description: "An ordered list of fields by which to group events. Each group is combined independently, allowing you to keep independent events separate. When no fields are specified, all events will be combined in a single group. Events missing a specified field will be combined in their own group. To keep metrics converted with the `metric_to_log` transform apart when they share a name across namespaces, group by both `name` and `namespace`."
 			required:    false
 			warnings: []
 			type: array: {
@@ -85,6 +178,28 @@ components: transforms: reduce: {
 				}
 			}
 		}
+		group_by_template: {
+			common:      false
+			description: "A template rendered for each event, whose result is used to group events instead of `group_by`. Events for which the template can't be rendered will be combined in their own group. Can't be used together with `group_by`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ region }}-{{ service }}"]
+				syntax: "template"
+			}
+		}
+		max_bytes_per_group: {
+			common:      false
+			description: "Flush a combined event as soon as the estimated size of the events merged into it exceeds this many bytes, counting every incoming field even when its merge strategy discards it. A single event over the limit is flushed on its own. By default the size is not limited."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [1048576]
+				unit: "bytes"
+			}
+		}
 		merge_strategies: {
 			common: false
 			description: """
@@ -92,6 +207,16 @@ components: transforms: reduce: {
 				field specified this strategy will be used for combining
 				events rather than the default behavior.
 
+				A field name ending in `.*`, such as `metrics.*`, applies the
+				strategy to every field below that prefix. A strategy for the
+				exact field name takes precedence over prefixes, and longer
+				prefixes take precedence over shorter ones.
+
+				The `arg_max` and `arg_min` strategies compare events by
+				another field, which is set with a table such as
+				`error = { strategy = "arg_max", by = "latency" }`. Numbers
+				are compared numerically and strings lexicographically.
+
 				The default behavior is as follows:
 
 				1. The first value of a string field is kept, subsequent
@@ -119,13 +244,16 @@ components: transforms: reduce: {
 						warnings: []
 						type: string: {
 							enum: {
+								arg_max:        "The value from the event with the greatest value of another field, such as the error at peak latency. Set as `{ strategy = \"arg_max\", by = \"latency\" }`."
+								arg_min:        "The value from the event with the least value of another field. Set as `{ strategy = \"arg_min\", by = \"latency\" }`."
 								array:          "Each value is appended to an array."
 								concat:         "Concatenate each string value (delimited with a space)."
 								concat_newline: "Concatenate each string value (delimited with a newline)."
 								discard:        "Discard all but the first value found."
+								drop:           "Drop the field from the reduced event entirely."
 								sum:            "Sum all numeric values."
-								max:            "The maximum of all numeric values."
-								min:            "The minimum of all numeric values."
+								max:            "The maximum of all numeric values, or the lexicographically greatest string if the first value is a string. Numbers and strings can't be compared, so values of the other kind are dropped."
+								min:            "The minimum of all numeric values, or the lexicographically least string if the first value is a string. Numbers and strings can't be compared, so values of the other kind are dropped."
 							}
 							syntax: "literal"
 						}
@@ -133,6 +261,24 @@ components: transforms: reduce: {
 				}
 			}
 		}
+		passthrough_originals: {
+			common:      false
+			description: "Whether each incoming event is also emitted as is, with a `reduce_original` field set to `true`, in addition to the combined events. This is meant for debugging how events are combined."
+			required:    false
+			warnings: ["Every event is emitted in addition to the combined events, which more than doubles the volume of events leaving this transform."]
+			type: bool: default: false
+		}
+		reason_key: {
+			common:      false
+			description: "If set, the reason a group was flushed is inserted into the combined event at this path. The reason is one of `ends_when`, `starts_when`, `expired`, `window`, `field_changed`, `shutdown`, or `max_bytes_per_group`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["reduce_reason"]
+				syntax: "literal"
+			}
+		}
 		starts_when: {
 			common: false
 			description: """
@@ -149,6 +295,16 @@ components: transforms: reduce: {
 				syntax: "literal"
 			}
 		}
+		window_ms: {
+			common:      false
+			description: "If set, each group is flushed once it is this old, even if events are still being combined into it. This produces one combined event per group and window, as opposed to `expire_after_ms`, which waits for a group to go idle. Can also be set as `max_lifetime_ms`, since it caps how long a group lives."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				unit:    "milliseconds"
+			}
+		}
 	}
 
 	input: {
@@ -267,6 +423,8 @@ components: transforms: reduce: {
 	]
 
 	telemetry: metrics: {
-		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
+		byte_limit_events_flushed_total: components.sources.internal_metrics.output.metrics.byte_limit_events_flushed_total
+		reduce_flush_ticks_total:        components.sources.internal_metrics.output.metrics.reduce_flush_ticks_total
+		stale_events_flushed_total:      components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
 }
@@ -128,6 +128,7 @@ components: transforms: tokenizer: {
 
 				* `"..."` - Quotes are used tp wrap phrases. Spaces are preserved, but the wrapping quotes will be discarded.
 				* `[...]` - Brackets are used to wrap phrases. Spaces are preserved, but the wrapping brackets will be discarded.
+				* `{...}` - Braces are used to wrap phrases. Spaces are preserved, but the wrapping braces will be discarded.
 				* `\` - Can be used to escape the above characters, Vector will treat them as literal.
 				"""#
 		}
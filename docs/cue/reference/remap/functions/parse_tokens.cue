@@ -6,8 +6,9 @@ remap: functions: parse_tokens: {
 		Parses the `value` in "token" format. A token is considered to be one of the following:
 
 		* A word surrounded by whitespace.
-		* Text delimited by double quotes: `".."`. Quotes can be included in the token if they are escaped by a backslash (`\`).
+		* Text delimited by double quotes: `".."`. Quotes can be included in the token if they are escaped by a backslash (`\`), and the backslash is removed from the token.
 		* Text delimited by square brackets: `[..]`. Closing square brackets can be included in the token if they are escaped by a backslash (`\`).
+		* Text delimited by curly braces: `{..}`. Closing curly braces can be included in the token if they are escaped by a backslash (`\`).
 		"""#
 	notices: [
 		"""
@@ -36,7 +37,7 @@ remap: functions: parse_tokens: {
 					"A sentence \"with \\\"a\\\" sentence inside\" and [some brackets]"
 				)
 				"""#
-			return: ["A", "sentence", #"with \"a\" sentence inside"#, "and", "some brackets"]
+			return: ["A", "sentence", #"with "a" sentence inside"#, "and", "some brackets"]
 		},
 	]
 }
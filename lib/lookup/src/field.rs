@@ -1,5 +1,6 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::borrow::Cow;
 
 lazy_static! {
     static ref VALID_FIELD: Regex = Regex::new("^[0-9]*[a-zA-Z_][0-9a-zA-Z_]*$").unwrap();
@@ -11,3 +12,56 @@ lazy_static! {
 pub(crate) fn is_valid_fieldname(name: &str) -> bool {
     VALID_FIELD.is_match(name)
 }
+
+/// Returns `true` if the field name must be quoted to be used in a lookup path, such as when it
+/// contains periods, spaces or brackets.
+pub fn needs_quoting(name: &str) -> bool {
+    !is_valid_fieldname(name)
+}
+
+/// Quote the field name if it can not be used in a lookup path as is. Quotes and backslashes in
+/// the name are escaped with a backslash.
+///
+/// ```rust
+/// use lookup::quote_field;
+/// assert_eq!(quote_field("foo"), "foo");
+/// assert_eq!(quote_field("foo.bar"), r#""foo.bar""#);
+/// assert_eq!(quote_field(r#"say "hi""#), r#""say \"hi\"""#);
+/// ```
+pub fn quote_field(name: &str) -> Cow<'_, str> {
+    if needs_quoting(name) {
+        Cow::Owned(format!(r#""{}""#, escape(name)))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Escape quotes and backslashes in a field name so it can be placed between quotes.
+pub(crate) fn escape(name: &str) -> Cow<'_, str> {
+    if name.contains(|c| c == '"' || c == '\\') {
+        Cow::Owned(name.replace('\\', r"\\").replace('"', r#"\""#))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Reverse `escape` for the contents of a quoted field. Backslashes that don't precede a quote or
+/// another backslash are kept as they are.
+pub(crate) fn unescape(name: &str) -> Cow<'_, str> {
+    if !name.contains('\\') {
+        return Cow::Borrowed(name);
+    }
+
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&next)) if next == '"' || next == '\\' => {
+                unescaped.push(next);
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    Cow::Owned(unescaped)
+}
@@ -0,0 +1,93 @@
+//! Conversions between lookups and [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901)
+//! JSON Pointers.
+use crate::{field, FieldBuf, LookupBuf, LookupError, Segment, SegmentBuf};
+use std::collections::VecDeque;
+
+/// Encode the segments as a JSON Pointer.
+///
+/// Only fields and non-negative indices have a JSON Pointer equivalent, any other segment is an
+/// error.
+///
+/// Field names are unescaped first, so fields parsed from a quoted path encode their actual names.
+pub(crate) fn encode<'a>(
+    segments: impl Iterator<Item = Segment<'a>>,
+) -> Result<String, LookupError> {
+    let mut pointer = String::new();
+    for segment in segments {
+        pointer.push('/');
+        match segment {
+            Segment::Field(field) => {
+                pointer.push_str(&field.unescaped_name().replace('~', "~0").replace('/', "~1"))
+            }
+            Segment::Index(i) if !i.is_negative() => pointer.push_str(&i.to_string()),
+            segment => {
+                return Err(LookupError::Invalid {
+                    message: format!(
+                        "segment `{}` can not be represented as a JSON Pointer",
+                        segment
+                    ),
+                })
+            }
+        }
+    }
+    Ok(pointer)
+}
+
+/// Decode a JSON Pointer into a `LookupBuf`.
+///
+/// Reference tokens consisting only of digits become indices, all others become fields.
+pub(crate) fn decode(pointer: &str) -> Result<LookupBuf, LookupError> {
+    if pointer.is_empty() {
+        return Ok(LookupBuf::root());
+    }
+
+    if !pointer.starts_with('/') {
+        return Err(LookupError::Invalid {
+            message: format!("JSON Pointer `{}` must start with `/`", pointer),
+        });
+    }
+
+    pointer[1..]
+        .split('/')
+        .map(|token| {
+            if is_index(token) {
+                if let Ok(index) = token.parse() {
+                    return Ok(SegmentBuf::Index(index));
+                }
+            }
+
+            let name = unescape(token).ok_or_else(|| LookupError::Invalid {
+                message: format!("JSON Pointer token `{}` has an invalid escape", token),
+            })?;
+            Ok(SegmentBuf::Field(FieldBuf {
+                requires_quoting: field::needs_quoting(&name),
+                name,
+            }))
+        })
+        .collect::<Result<VecDeque<_>, _>>()
+        .map(LookupBuf::from)
+}
+
+/// Array indices in JSON Pointers are digits without leading zeros.
+fn is_index(token: &str) -> bool {
+    !token.is_empty()
+        && token.bytes().all(|b| b.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'))
+}
+
+/// Reverse the `~0` and `~1` escapes, returning `None` if any other `~` sequence is found.
+fn unescape(token: &str) -> Option<String> {
+    let mut name = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next() {
+                Some('0') => name.push('~'),
+                Some('1') => name.push('/'),
+                _ => return None,
+            },
+            c => name.push(c),
+        }
+    }
+    Some(name)
+}
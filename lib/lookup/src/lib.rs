@@ -2,11 +2,13 @@ use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 pub use error::LookupError;
+pub use field::quote_field;
 pub use lookup_buf::{FieldBuf, LookupBuf, SegmentBuf};
 pub use lookup_view::{Field, Lookup, Segment};
 
 mod error;
 mod field;
+mod json_pointer;
 mod lookup_buf;
 mod lookup_view;
 pub mod parser;
@@ -40,6 +42,10 @@ pub trait Look<'a>:
 
     fn starts_with(&self, needle: &Self) -> bool;
 
+    fn strip_prefix(&self, needle: &Self) -> Option<Self>;
+
+    fn parent(&self) -> Option<Self>;
+
     fn is_root(&self) -> bool;
 }
 
@@ -63,6 +69,14 @@ pub trait LookSegment<'a>:
 
     fn is_index(&self) -> bool;
 
+    fn index_wildcard() -> Self;
+
+    fn is_index_wildcard(&self) -> bool;
+
+    fn range(start: Option<usize>, end: Option<usize>) -> Self;
+
+    fn is_range(&self) -> bool;
+
     fn coalesce(v: Vec<Self::Field>) -> Self;
 
     fn is_coalesce(&self) -> bool;
@@ -1,4 +1,4 @@
-use crate::{Look, Lookup, LookupError};
+use crate::{Look, Lookup, LookupError, Segment};
 use inherent::inherent;
 #[cfg(any(test, feature = "arbitrary"))]
 use quickcheck::{Arbitrary, Gen};
@@ -116,8 +116,12 @@ impl Display for LookupBuf {
             match (segment, maybe_next) {
                 (SegmentBuf::Field(_), true) => write!(f, r#"{}."#, segment)?,
                 (SegmentBuf::Field(_), false) => write!(f, "{}", segment)?,
-                (SegmentBuf::Index(_), true) => write!(f, r#"[{}]."#, segment)?,
-                (SegmentBuf::Index(_), false) => write!(f, "[{}]", segment)?,
+                (SegmentBuf::Index(_), true)
+                | (SegmentBuf::IndexWildcard, true)
+                | (SegmentBuf::Range { .. }, true) => write!(f, r#"[{}]."#, segment)?,
+                (SegmentBuf::Index(_), false)
+                | (SegmentBuf::IndexWildcard, false)
+                | (SegmentBuf::Range { .. }, false) => write!(f, "[{}]", segment)?,
                 (SegmentBuf::Coalesce(_), true) => write!(f, r#"{}."#, segment)?,
                 (SegmentBuf::Coalesce(_), false) => write!(f, "{}", segment)?,
             }
@@ -157,6 +161,79 @@ impl LookupBuf {
         &self.segments
     }
 
+    /// Push a field onto the end of the lookup.
+    ///
+    /// Unlike pushing a `&str` or `String`, the name is taken verbatim (surrounding quotes are
+    /// kept as part of the name) and is quoted when displayed if needed.
+    pub fn push_field(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.segments.push_back(SegmentBuf::Field(FieldBuf {
+            requires_quoting: SegmentBuf::needs_quoting(&name),
+            name,
+        }));
+    }
+
+    /// Parse an [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON Pointer, such as
+    /// `/foo/0/bar`. Reference tokens consisting only of digits are read as indices.
+    pub fn from_json_pointer(pointer: &str) -> Result<Self, LookupError> {
+        crate::json_pointer::decode(pointer)
+    }
+
+    /// Represent the lookup as an [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON
+    /// Pointer, such as `/foo/0/bar`.
+    ///
+    /// Coalesces, wildcards, ranges and negative indices have no JSON Pointer equivalent.
+    pub fn to_json_pointer(&self) -> Result<String, LookupError> {
+        crate::json_pointer::encode(self.iter().map(Segment::from))
+    }
+
+    /// Parse a simple JSONPath expression, such as `$.foo.bar[0]`.
+    ///
+    /// A leading `$` or `$.` is stripped and the remainder is parsed like any other lookup, so
+    /// fields must be written in dot notation. Recursive descent (`..`) and filters (`[?(...)]`)
+    /// have no lookup equivalent and are rejected.
+    pub fn from_jsonpath(path: &str) -> Result<Self, LookupError> {
+        let unsupported = |feature: &str| LookupError::Invalid {
+            message: format!("JSONPath `{}` uses unsupported {}", path, feature),
+        };
+
+        let rest = path.strip_prefix('$').unwrap_or(path);
+        if rest.starts_with("..") {
+            return Err(unsupported("recursive descent"));
+        }
+        let rest = rest.strip_prefix('.').unwrap_or(rest);
+
+        let mut quoted = false;
+        let mut bracketed = false;
+        let mut chars = rest.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if quoted => {
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                _ if quoted => {}
+                '[' => {
+                    bracketed = true;
+                    if chars.peek() == Some(&'?') {
+                        return Err(unsupported("filters"));
+                    }
+                }
+                ']' => bracketed = false,
+                '.' if !bracketed && chars.peek() == Some(&'.') => {
+                    return Err(unsupported("recursive descent"));
+                }
+                _ => {}
+            }
+        }
+
+        if rest.is_empty() {
+            Ok(Self::root())
+        } else {
+            rest.parse()
+        }
+    }
+
     /// Create the possible fields that can be followed by this lookup.
     /// Because of coalesced paths there can be a number of different combinations.
     /// There is the potential for this function to create a vast number of different
@@ -191,7 +268,7 @@ impl LookupBuf {
                         .collect();
                 }
 
-                SegmentBuf::Index(_) => {
+                SegmentBuf::Index(_) | SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => {
                     return Vec::new();
                 }
             }
@@ -246,7 +323,29 @@ impl Look<'static> for LookupBuf {
 
     /// Returns `true` if `needle` is a prefix of the lookup.
     fn starts_with(&self, needle: &LookupBuf) -> bool {
-        needle.iter().zip(&self.segments).all(|(n, s)| n == s)
+        needle.len() <= self.len() && needle.iter().zip(&self.segments).all(|(n, s)| n == s)
+    }
+
+    /// Returns the remainder of the lookup after `needle`, or `None` if `needle` is not a prefix.
+    fn strip_prefix(&self, needle: &LookupBuf) -> Option<LookupBuf> {
+        if self.starts_with(needle) {
+            Some(LookupBuf {
+                segments: self.segments.iter().skip(needle.len()).cloned().collect(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the lookup without its final segment, or `None` if it has fewer than two segments.
+    fn parent(&self) -> Option<LookupBuf> {
+        if self.len() < 2 {
+            None
+        } else {
+            Some(LookupBuf {
+                segments: self.segments.iter().take(self.len() - 1).cloned().collect(),
+            })
+        }
     }
 }
 
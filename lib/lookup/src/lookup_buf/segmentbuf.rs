@@ -1,9 +1,13 @@
-use crate::{field, LookSegment, Segment};
+use crate::{field, quote_field, LookSegment, Segment};
 use inherent::inherent;
 #[cfg(any(test, feature = "arbitrary"))]
 use quickcheck::{Arbitrary, Gen};
 use std::fmt::{Display, Formatter};
 
+/// An owned field name.
+///
+/// The name is stored unescaped, so it matches the key it addresses, and is escaped again when
+/// displayed.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub struct FieldBuf {
     pub name: String,
@@ -20,9 +24,9 @@ impl FieldBuf {
 impl Display for FieldBuf {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         if self.requires_quoting {
-            write!(formatter, r#""{}""#, self.name)
+            write!(formatter, r#""{}""#, field::escape(&self.name))
         } else {
-            write!(formatter, "{}", self.name)
+            write!(formatter, "{}", quote_field(&self.name))
         }
     }
 }
@@ -35,9 +39,9 @@ impl From<String> for FieldBuf {
             // There is unfortunately no way to make an owned substring of a string.
             // So we have to take a slice and clone it.
             let len = name.len();
-            name = name[1..len - 1].to_string();
+            name = field::unescape(&name[1..len - 1]).into_owned();
             requires_quoting = true;
-        } else if !field::is_valid_fieldname(&name) {
+        } else if field::needs_quoting(&name) {
             requires_quoting = true
         }
 
@@ -83,15 +87,31 @@ impl Arbitrary for FieldBuf {
 ///
 /// They represent either a field or an index. A sequence of `SegmentBuf`s can become a `LookupBuf`.
 ///
+/// Like `Segment`s, they order structurally: fields by name and indices numerically.
+///
 /// This is the owned, allocated side of a `Segement` for `LookupBuf.` It owns its fields unlike `Lookup`. Think of `String` to `&str` or `PathBuf` to `Path`.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub enum SegmentBuf {
     Field(FieldBuf),
     Index(isize), // Indexes can be negative.
+    // Wildcards address every index of an array.
+    IndexWildcard,
+    // Ranges address a slice of an array, either bound may be left open.
+    Range {
+        start: Option<usize>,
+        end: Option<usize>,
+    },
     // Coalesces hold multiple possible fields.
     Coalesce(Vec<FieldBuf>),
 }
 
+impl SegmentBuf {
+    /// Returns `true` if the field name must be quoted to be used in a lookup path.
+    pub fn needs_quoting(name: &str) -> bool {
+        field::needs_quoting(name)
+    }
+}
+
 #[cfg(any(test, feature = "arbitrary"))]
 impl Arbitrary for SegmentBuf {
     fn arbitrary(g: &mut Gen) -> Self {
@@ -112,6 +132,7 @@ impl Arbitrary for SegmentBuf {
         match self {
             SegmentBuf::Field(field) => Box::new(field.shrink().map(SegmentBuf::Field)),
             SegmentBuf::Index(index) => Box::new(index.shrink().map(SegmentBuf::Index)),
+            SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => quickcheck::empty_shrinker(),
             SegmentBuf::Coalesce(fields) => Box::new(
                 fields
                     .shrink()
@@ -142,6 +163,22 @@ impl<'a> LookSegment<'a> for SegmentBuf {
         matches!(self, SegmentBuf::Index(_))
     }
 
+    fn index_wildcard() -> SegmentBuf {
+        SegmentBuf::IndexWildcard
+    }
+
+    fn is_index_wildcard(&self) -> bool {
+        matches!(self, SegmentBuf::IndexWildcard)
+    }
+
+    fn range(start: Option<usize>, end: Option<usize>) -> SegmentBuf {
+        SegmentBuf::Range { start, end }
+    }
+
+    fn is_range(&self) -> bool {
+        matches!(self, SegmentBuf::Range { .. })
+    }
+
     fn coalesce(v: Vec<FieldBuf>) -> SegmentBuf {
         SegmentBuf::Coalesce(v)
     }
@@ -155,6 +192,17 @@ impl Display for SegmentBuf {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             SegmentBuf::Index(i) => write!(formatter, "{}", i),
+            SegmentBuf::IndexWildcard => write!(formatter, "*"),
+            SegmentBuf::Range { start, end } => {
+                if let Some(start) = start {
+                    write!(formatter, "{}", start)?;
+                }
+                write!(formatter, "..")?;
+                if let Some(end) = end {
+                    write!(formatter, "{}", end)?;
+                }
+                Ok(())
+            }
             SegmentBuf::Field(field) => write!(formatter, "{}", field),
             SegmentBuf::Coalesce(v) => write!(
                 formatter,
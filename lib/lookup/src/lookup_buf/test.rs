@@ -41,6 +41,16 @@ fn field_is_quoted() {
     );
 }
 
+#[test]
+fn root() {
+    let lookup = LookupBuf::root();
+    assert!(lookup.is_root());
+    assert!(lookup.is_empty());
+    assert_eq!(lookup, LookupBuf::default());
+    assert_eq!(lookup.to_string(), "");
+    assert!(!LookupBuf::from_str("foo").unwrap().is_root());
+}
+
 #[test]
 fn zero_len_not_allowed() {
     let input = "";
@@ -76,6 +86,22 @@ fn quoted() {
     assert_eq!(lookup.to_string(), input);
 }
 
+#[test]
+fn quoted_with_escapes() {
+    let input = r#"message."he said \"hi\""."back\\slash"[0]"#;
+    let lookup = LookupBuf::from_str(input).unwrap();
+    assert_eq!(lookup[1], SegmentBuf::from(r#"he said "hi""#));
+    assert_eq!(lookup[2], SegmentBuf::from(r#"back\slash"#));
+    assert_eq!(lookup.to_string(), input);
+
+    let mut pushed = LookupBuf::from("message");
+    pushed.push_field(r#"he said "hi""#);
+    pushed.push_field(r#"back\slash"#);
+    pushed.push_back(0);
+    assert_eq!(pushed, lookup);
+    assert_eq!(pushed.to_string().parse::<LookupBuf>().unwrap(), pushed);
+}
+
 #[test]
 fn coalesced() {
     let input = "plain.(option_one | option_two)";
@@ -97,6 +123,28 @@ fn coalesced_nesting() {
     assert!(LookupBuf::from_str(input).is_err());
 }
 
+#[test]
+fn coalesced_within_path() {
+    let input = "snoot.(boop | beep).deeper[0].(a | b | c)";
+    let lookup = LookupBuf::from_str(input).unwrap();
+    assert_eq!(lookup[0], SegmentBuf::from("snoot"));
+    assert_eq!(
+        lookup[1],
+        SegmentBuf::from(vec![FieldBuf::from("boop"), FieldBuf::from("beep")])
+    );
+    assert_eq!(lookup[2], SegmentBuf::from("deeper"));
+    assert_eq!(lookup[3], SegmentBuf::from(0));
+    assert_eq!(
+        lookup[4],
+        SegmentBuf::from(vec![
+            FieldBuf::from("a"),
+            FieldBuf::from("b"),
+            FieldBuf::from("c"),
+        ])
+    );
+    assert_eq!(lookup.to_string(), input);
+}
+
 #[test]
 fn push() {
     let input = "some_key";
@@ -123,6 +171,47 @@ fn array() {
     assert_eq!(lookup.to_string(), input);
 }
 
+#[test]
+fn negative_array() {
+    let input = "foo[-1]";
+    let lookup = LookupBuf::from_str(input).unwrap();
+    assert_eq!(lookup[0], SegmentBuf::from(String::from("foo")));
+    assert_eq!(lookup[1], SegmentBuf::from(-1));
+    assert_eq!(lookup.to_string(), input);
+}
+
+#[test]
+fn wildcard_array() {
+    let input = "foo[*].bar";
+    let lookup = LookupBuf::from_str(input).unwrap();
+    assert_eq!(lookup[0], SegmentBuf::from(String::from("foo")));
+    assert_eq!(lookup[1], SegmentBuf::IndexWildcard);
+    assert_eq!(lookup[2], SegmentBuf::from(String::from("bar")));
+    assert_eq!(lookup.to_string(), input);
+}
+
+#[test]
+fn range_array() {
+    let cases = [
+        ("foo[0..2]", Some(0), Some(2)),
+        ("foo[..2]", None, Some(2)),
+        ("foo[1..]", Some(1), None),
+        ("foo[..]", None, None),
+    ];
+    for (input, start, end) in cases.iter() {
+        let lookup = LookupBuf::from_str(input).unwrap();
+        assert_eq!(lookup[0], SegmentBuf::from(String::from("foo")));
+        assert_eq!(
+            lookup[1],
+            SegmentBuf::Range {
+                start: *start,
+                end: *end
+            }
+        );
+        assert_eq!(lookup.to_string(), *input);
+    }
+}
+
 #[test]
 fn via_parse() {
     let input = "foo[0]";
@@ -139,6 +228,82 @@ fn to_string() {
     assert_eq!(lookup.to_string(), input);
 }
 
+#[test]
+fn starts_with_and_strip_prefix() {
+    let lookup = LookupBuf::from_str(SUFFICIENTLY_COMPLEX).unwrap();
+
+    for len in 0..=SUFFICIENTLY_DECOMPOSED.len() {
+        let prefix = LookupBuf::from_segments(SUFFICIENTLY_DECOMPOSED[..len].to_vec());
+        let rest = LookupBuf::from_segments(SUFFICIENTLY_DECOMPOSED[len..].to_vec());
+        assert!(lookup.starts_with(&prefix), "Failed at {}", len);
+        assert_eq!(
+            lookup.strip_prefix(&prefix),
+            Some(rest),
+            "Failed at {}",
+            len
+        );
+    }
+
+    let mut longer = lookup.clone();
+    longer.push_back("extra");
+    assert!(!lookup.starts_with(&longer));
+    assert_eq!(lookup.strip_prefix(&longer), None);
+
+    let other = LookupBuf::from_str("regular.unquoted").unwrap();
+    assert!(!lookup.starts_with(&other));
+    assert_eq!(lookup.strip_prefix(&other), None);
+}
+
+#[test]
+fn parent() {
+    let lookup = LookupBuf::from_str("foo[0].bar").unwrap();
+    assert_eq!(
+        lookup.parent(),
+        Some(LookupBuf::from_str("foo[0]").unwrap())
+    );
+
+    let lookup = LookupBuf::from_str("foo.bar[0]").unwrap();
+    assert_eq!(
+        lookup.parent(),
+        Some(LookupBuf::from_str("foo.bar").unwrap())
+    );
+
+    let lookup = LookupBuf::from_str("foo").unwrap();
+    assert_eq!(lookup.parent(), None);
+    assert_eq!(LookupBuf::root().parent(), None);
+}
+
+#[test]
+fn sorts_structurally() {
+    let mut lookups = vec![
+        "foo[10]",
+        "foo.bar",
+        "foo[2]",
+        "bar",
+        "foo[2].baz",
+        "foo[-1]",
+        "foo",
+    ]
+    .into_iter()
+    .map(|s| LookupBuf::from_str(s).unwrap())
+    .collect::<Vec<_>>();
+    lookups.sort();
+
+    let sorted = lookups.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        sorted,
+        vec![
+            "bar",
+            "foo",
+            "foo.bar",
+            "foo[-1]",
+            "foo[2]",
+            "foo[2].baz",
+            "foo[10]"
+        ]
+    );
+}
+
 #[test]
 fn impl_index_usize() {
     let lookup = LookupBuf::from_str(SUFFICIENTLY_COMPLEX).unwrap();
@@ -225,6 +390,100 @@ fn lookup_to_string_and_serialize() {
         });
 }
 
+#[test]
+fn deserialize_quoted_segments() {
+    let serialized = serde_json::to_string(SUFFICIENTLY_COMPLEX).unwrap();
+    let lookup: LookupBuf = serde_json::from_str(&serialized).unwrap();
+    let mut iter = lookup.iter();
+    for (index, expected) in SUFFICIENTLY_DECOMPOSED.iter().enumerate() {
+        let parsed = iter
+            .next()
+            .unwrap_or_else(|| panic!("Expected at index {}: {:?}, got None.", index, expected));
+        assert_eq!(expected, parsed, "Failed at {}", index);
+    }
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn json_pointer_round_trip() {
+    let lookup = LookupBuf::from_str(SUFFICIENTLY_COMPLEX).unwrap();
+    let pointer = lookup.to_json_pointer().unwrap();
+    assert_eq!(
+        pointer,
+        "/regular/quoted/quoted but spaces/quoted.but.periods/lookup/0/nested_lookup/0/0"
+    );
+
+    let parsed = LookupBuf::from_json_pointer(&pointer).unwrap();
+    assert_eq!(parsed.len(), SUFFICIENTLY_DECOMPOSED.len());
+    assert_eq!(parsed.to_json_pointer().unwrap(), pointer);
+    assert_eq!(
+        parsed.to_string(),
+        r#"regular.quoted."quoted but spaces"."quoted.but.periods".lookup[0].nested_lookup[0][0]"#
+    );
+}
+
+#[test]
+fn json_pointer_escapes() {
+    let mut lookup = LookupBuf::root();
+    lookup.push_back("a/b");
+    lookup.push_back("m~n");
+    lookup.push_back(1);
+    let pointer = lookup.to_json_pointer().unwrap();
+    assert_eq!(pointer, "/a~1b/m~0n/1");
+    assert_eq!(LookupBuf::from_json_pointer(&pointer).unwrap(), lookup);
+}
+
+#[test]
+fn json_pointer_edge_cases() {
+    assert_eq!(LookupBuf::from_json_pointer("").unwrap(), LookupBuf::root());
+    assert_eq!(LookupBuf::root().to_json_pointer().unwrap(), "");
+    // Leading zeros mean the token isn't an index.
+    assert_eq!(
+        LookupBuf::from_json_pointer("/foo/01").unwrap()[1],
+        SegmentBuf::from("01")
+    );
+    assert!(LookupBuf::from_json_pointer("foo").is_err());
+    assert!(LookupBuf::from_json_pointer("/foo~2").is_err());
+    assert!(LookupBuf::from_str("foo[-1]")
+        .unwrap()
+        .to_json_pointer()
+        .is_err());
+    assert!(LookupBuf::from_str("(a | b)")
+        .unwrap()
+        .to_json_pointer()
+        .is_err());
+}
+
+#[test]
+fn needs_quoting() {
+    assert!(!SegmentBuf::needs_quoting("plain"));
+    assert!(!SegmentBuf::needs_quoting("00numericstart"));
+    assert!(SegmentBuf::needs_quoting("with spaces"));
+    assert!(SegmentBuf::needs_quoting("with.periods"));
+    assert!(SegmentBuf::needs_quoting("with[brackets]"));
+    assert!(Segment::needs_quoting("with[brackets]"));
+
+    assert_eq!(quote_field("plain"), "plain");
+    assert_eq!(quote_field("with spaces"), r#""with spaces""#);
+    assert_eq!(quote_field("with.periods"), r#""with.periods""#);
+    assert_eq!(quote_field("with[brackets]"), r#""with[brackets]""#);
+    assert_eq!(quote_field(r#"with "quotes""#), r#""with \"quotes\"""#);
+    assert_eq!(quote_field(r#"with\backslash"#), r#""with\\backslash""#);
+}
+
+#[test]
+fn push_field() {
+    let mut lookup = LookupBuf::root();
+    lookup.push_field("plain");
+    lookup.push_field("with spaces");
+    lookup.push_field("with.periods");
+    lookup.push_field("with[brackets]");
+
+    let expected = r#"plain."with spaces"."with.periods"."with[brackets]""#;
+    assert_eq!(lookup.to_string(), expected);
+    assert_eq!(LookupBuf::from_str(expected).unwrap(), lookup);
+}
+
 #[test]
 fn test_indexed_coalesce_from_string() {
     let parsed = LookupBuf::from_str("(a | b)[2]");
@@ -238,6 +497,13 @@ fn test_index_parses() {
     assert_eq!("[30]", parsed.to_string());
 }
 
+#[test]
+fn test_negative_index_parses() {
+    let parsed = LookupBuf::from_str("[-30]").unwrap();
+    assert_eq!(LookupBuf::from(SegmentBuf::Index(-30)), parsed);
+    assert_eq!("[-30]", parsed.to_string());
+}
+
 #[test]
 fn parses() {
     fn inner(path: LookupBuf) -> TestResult {
@@ -253,3 +519,28 @@ fn parses() {
         .max_tests(2_000)
         .quickcheck(inner as fn(LookupBuf) -> TestResult);
 }
+
+#[test]
+fn from_jsonpath() {
+    let expected = LookupBuf::from_str("a.b[0]").unwrap();
+    assert_eq!(LookupBuf::from_jsonpath("$.a.b[0]").unwrap(), expected);
+    assert_eq!(LookupBuf::from_jsonpath("a.b[0]").unwrap(), expected);
+    assert_eq!(
+        LookupBuf::from_jsonpath("$[0].a").unwrap(),
+        LookupBuf::from_str("[0].a").unwrap()
+    );
+    assert_eq!(
+        LookupBuf::from_jsonpath(r#"$."a..b".c[1..3]"#).unwrap(),
+        LookupBuf::from_str(r#""a..b".c[1..3]"#).unwrap()
+    );
+    assert_eq!(LookupBuf::from_jsonpath("$").unwrap(), LookupBuf::root());
+}
+
+#[test]
+fn from_jsonpath_unsupported() {
+    for path in &["$..a", "$.a..b", "$.a[?(@.b)]"] {
+        let error = LookupBuf::from_jsonpath(path).unwrap_err();
+        assert!(error.to_string().contains("unsupported"), "{}", error);
+    }
+    assert!(LookupBuf::from_jsonpath("$.a[0:2]").is_err());
+}
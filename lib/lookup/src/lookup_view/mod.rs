@@ -1,10 +1,10 @@
-use crate::{Look, LookupBuf, LookupError, SegmentBuf};
+use crate::{field, FieldBuf, Look, LookupBuf, LookupError, SegmentBuf};
 use core::fmt;
 use inherent::inherent;
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{Display, Formatter};
-use std::ops::{Index, IndexMut};
+use std::ops::{Index, IndexMut, RangeBounds};
 use std::{collections::VecDeque, iter::IntoIterator, str};
 
 #[cfg(test)]
@@ -94,8 +94,12 @@ impl<'a> Display for Lookup<'a> {
             match (segment, maybe_next) {
                 (Segment::Field(_), true) => write!(f, r#"{}."#, segment)?,
                 (Segment::Field(_), false) => write!(f, "{}", segment)?,
-                (Segment::Index(_), true) => write!(f, r#"[{}]."#, segment)?,
-                (Segment::Index(_), false) => write!(f, "[{}]", segment)?,
+                (Segment::Index(_), true)
+                | (Segment::IndexWildcard, true)
+                | (Segment::Range { .. }, true) => write!(f, r#"[{}]."#, segment)?,
+                (Segment::Index(_), false)
+                | (Segment::IndexWildcard, false)
+                | (Segment::Range { .. }, false) => write!(f, "[{}]", segment)?,
                 (Segment::Coalesce(_), true) => write!(f, r#"{}."#, segment)?,
                 (Segment::Coalesce(_), false) => write!(f, "{}", segment)?,
             }
@@ -120,6 +124,75 @@ impl<'a> Lookup<'a> {
     pub fn into_buf(self) -> LookupBuf {
         LookupBuf::from(self)
     }
+
+    /// Represent the lookup as an [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON
+    /// Pointer, such as `/foo/0/bar`.
+    ///
+    /// Coalesces, wildcards, ranges and negative indices have no JSON Pointer equivalent.
+    pub fn to_json_pointer(&self) -> Result<String, LookupError> {
+        crate::json_pointer::encode(self.iter().cloned())
+    }
+
+    /// Returns a `LookupBuf` of this lookup's segments followed by all of `other`'s.
+    ///
+    /// Unlike `extend`, neither lookup is modified and the lifetimes don't need to match.
+    pub fn join(&self, other: &Lookup<'_>) -> LookupBuf {
+        self.iter()
+            .chain(other.iter())
+            .cloned()
+            .map(SegmentBuf::from)
+            .collect::<VecDeque<_>>()
+            .into()
+    }
+
+    /// Returns a lookup of the segments in `range`, such as `lookup.slice(1..)` for everything
+    /// after the first segment. The segments still borrow from the original buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, the same as slicing a `Vec`.
+    pub fn slice<R>(&self, range: R) -> Lookup<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        Lookup {
+            segments: self.segments.range(range).cloned().collect(),
+        }
+    }
+
+    /// Returns the longest run of leading segments that `a` and `b` share, such as `foo.bar` for
+    /// `foo.bar.baz` and `foo.bar[0]`. This is the root if their first segments differ.
+    pub fn common_prefix(a: &Lookup<'a>, b: &Lookup<'_>) -> Lookup<'a> {
+        Lookup {
+            segments: a
+                .iter()
+                .zip(b.iter())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.clone())
+                .collect(),
+        }
+    }
+
+    /// Returns a key that orders lookups the way `all_fields` in `vector_core` orders the paths it
+    /// yields, so that a set of lookups can be sorted to match it.
+    ///
+    /// Keys compare segment by segment, with fields compared by their unescaped name and indices
+    /// compared numerically, since arrays are traversed in order. So `foo[2]` sorts before
+    /// `foo[10]`, and a path sorts right before the paths below it. Whether a field was quoted
+    /// doesn't affect the order.
+    pub fn to_alphabetical_sort_key(&self) -> LookupBuf {
+        self.iter()
+            .cloned()
+            .map(|segment| match SegmentBuf::from(segment) {
+                SegmentBuf::Field(FieldBuf { name, .. }) => SegmentBuf::Field(FieldBuf {
+                    requires_quoting: field::needs_quoting(&name),
+                    name,
+                }),
+                segment => segment,
+            })
+            .collect::<VecDeque<_>>()
+            .into()
+    }
 }
 
 #[inherent(pub)]
@@ -170,7 +243,29 @@ impl<'a> Look<'a> for Lookup<'a> {
 
     /// Returns `true` if `needle` is a prefix of the lookup.
     fn starts_with(&self, needle: &Lookup<'a>) -> bool {
-        needle.iter().zip(&self.segments).all(|(n, s)| n == s)
+        needle.len() <= self.len() && needle.iter().zip(&self.segments).all(|(n, s)| n == s)
+    }
+
+    /// Returns the remainder of the lookup after `needle`, or `None` if `needle` is not a prefix.
+    fn strip_prefix(&self, needle: &Lookup<'a>) -> Option<Lookup<'a>> {
+        if self.starts_with(needle) {
+            Some(Lookup {
+                segments: self.segments.iter().skip(needle.len()).cloned().collect(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the lookup without its final segment, or `None` if it has fewer than two segments.
+    fn parent(&self) -> Option<Lookup<'a>> {
+        if self.len() < 2 {
+            None
+        } else {
+            Some(Lookup {
+                segments: self.segments.iter().take(self.len() - 1).cloned().collect(),
+            })
+        }
     }
 }
 
@@ -1,29 +1,79 @@
-use crate::{field, FieldBuf, LookSegment, SegmentBuf};
+use crate::{field, quote_field, FieldBuf, LookSegment, SegmentBuf};
 use inherent::inherent;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+/// A borrowed field name.
+///
+/// Names parsed from a path borrow the path's text, so escaped quotes and backslashes inside a
+/// quoted field are kept escaped, and `escaped` is set. Names borrowed from a `FieldBuf` are
+/// already unescaped. Fields compare, hash and order by their unescaped name, so both forms of the
+/// same name are equal.
+#[derive(Debug, Clone)]
 pub struct Field<'a> {
     pub name: &'a str,
     // This is a very lazy optimization to avoid having to scan for escapes.
     pub requires_quoting: bool,
+    // Whether `name` still holds the escapes of a quoted field.
+    pub escaped: bool,
 }
 
 impl<'a> Field<'a> {
+    /// The name with any escapes resolved.
+    pub fn unescaped_name(&self) -> Cow<'a, str> {
+        if self.escaped {
+            field::unescape(self.name)
+        } else {
+            Cow::Borrowed(self.name)
+        }
+    }
+
     pub fn as_field_buf(&self) -> FieldBuf {
         FieldBuf {
-            name: self.name.to_string(),
+            name: self.unescaped_name().into_owned(),
             requires_quoting: self.requires_quoting,
         }
     }
 }
 
+impl<'a> PartialEq for Field<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.requires_quoting == other.requires_quoting
+            && self.unescaped_name() == other.unescaped_name()
+    }
+}
+
+impl<'a> Eq for Field<'a> {}
+
+impl<'a> PartialOrd for Field<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for Field<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.unescaped_name()
+            .cmp(&other.unescaped_name())
+            .then(self.requires_quoting.cmp(&other.requires_quoting))
+    }
+}
+
+impl<'a> Hash for Field<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.unescaped_name().hash(state);
+        self.requires_quoting.hash(state);
+    }
+}
+
 impl<'a> Display for Field<'a> {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        if self.requires_quoting {
-            write!(formatter, r#""{}""#, self.name)
-        } else {
-            write!(formatter, r#"{}"#, self.name)
+        match (self.requires_quoting, self.escaped) {
+            (true, true) => write!(formatter, r#""{}""#, self.name),
+            (true, false) => write!(formatter, r#""{}""#, field::escape(self.name)),
+            (false, _) => write!(formatter, "{}", quote_field(self.name)),
         }
     }
 }
@@ -31,18 +81,21 @@ impl<'a> Display for Field<'a> {
 impl<'a> From<&'a str> for Field<'a> {
     fn from(mut name: &'a str) -> Self {
         let mut requires_quoting = false;
+        let mut escaped = false;
 
         if name.starts_with('\"') && name.ends_with('\"') {
             let len = name.len();
             name = &name[1..len - 1];
             requires_quoting = true;
-        } else if !field::is_valid_fieldname(name) {
+            escaped = true;
+        } else if field::needs_quoting(name) {
             requires_quoting = true;
         }
 
         Self {
             name,
             requires_quoting,
+            escaped,
         }
     }
 }
@@ -52,6 +105,7 @@ impl<'a> From<&'a FieldBuf> for Field<'a> {
         Self {
             name: &v.name,
             requires_quoting: v.requires_quoting,
+            escaped: false,
         }
     }
 }
@@ -59,20 +113,37 @@ impl<'a> From<&'a FieldBuf> for Field<'a> {
 /// Segments are chunks of a lookup. They represent either a field or an index.
 /// A sequence of Segments can become a lookup.
 ///
+/// Segments order structurally: fields by name and indices numerically, so `foo[2]` sorts before
+/// `foo[10]`.
+///
 /// If you need an owned, allocated version, see `SegmentBuf`.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub enum Segment<'a> {
     Field(Field<'a>),
     Index(isize),
+    // Wildcards address every index of an array.
+    IndexWildcard,
+    // Ranges address a slice of an array, either bound may be left open.
+    Range {
+        start: Option<usize>,
+        end: Option<usize>,
+    },
     // Coalesces hold multiple possible fields.
     Coalesce(Vec<Field<'a>>),
 }
 
 impl<'a> Segment<'a> {
+    /// Returns `true` if the field name must be quoted to be used in a lookup path.
+    pub fn needs_quoting(name: &str) -> bool {
+        field::needs_quoting(name)
+    }
+
     pub fn as_segment_buf(&self) -> SegmentBuf {
         match self {
             Segment::Field(field) => SegmentBuf::field(field.as_field_buf()),
             Segment::Index(i) => SegmentBuf::index(*i),
+            Segment::IndexWildcard => SegmentBuf::index_wildcard(),
+            Segment::Range { start, end } => SegmentBuf::range(*start, *end),
             Segment::Coalesce(v) => {
                 SegmentBuf::coalesce(v.iter().map(|field| field.as_field_buf()).collect())
             }
@@ -105,6 +176,22 @@ impl<'a> LookSegment<'a> for Segment<'a> {
         matches!(self, Segment::Index(_))
     }
 
+    fn index_wildcard() -> Segment<'a> {
+        Segment::IndexWildcard
+    }
+
+    fn is_index_wildcard(&self) -> bool {
+        matches!(self, Segment::IndexWildcard)
+    }
+
+    fn range(start: Option<usize>, end: Option<usize>) -> Segment<'a> {
+        Segment::Range { start, end }
+    }
+
+    fn is_range(&self) -> bool {
+        matches!(self, Segment::Range { .. })
+    }
+
     fn coalesce(v: Vec<Field<'a>>) -> Segment<'a> {
         Segment::Coalesce(v)
     }
@@ -118,9 +205,21 @@ impl<'a> Display for Segment<'a> {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             Segment::Index(i) => write!(formatter, "{}", i),
+            Segment::IndexWildcard => write!(formatter, "*"),
+            Segment::Range { start, end } => {
+                if let Some(start) = start {
+                    write!(formatter, "{}", start)?;
+                }
+                write!(formatter, "..")?;
+                if let Some(end) = end {
+                    write!(formatter, "{}", end)?;
+                }
+                Ok(())
+            }
             Segment::Field(Field {
                 name,
                 requires_quoting: false,
+                ..
             }) => write!(formatter, "{}", name),
             Segment::Field(field) => write!(formatter, "{}", field),
             Segment::Coalesce(v) => write!(
@@ -158,6 +257,8 @@ impl<'a> From<&'a SegmentBuf> for Segment<'a> {
         match v {
             SegmentBuf::Field(field) => Self::Field(field.into()),
             SegmentBuf::Index(i) => Self::index(*i),
+            SegmentBuf::IndexWildcard => Self::index_wildcard(),
+            SegmentBuf::Range { start, end } => Self::range(*start, *end),
             SegmentBuf::Coalesce(v) => Self::coalesce(v.iter().map(|field| field.into()).collect()),
         }
     }
@@ -26,6 +26,7 @@ fn field_is_quoted() {
         Field {
             name: "zork2",
             requires_quoting: false,
+            escaped: false,
         },
         field
     );
@@ -35,11 +36,22 @@ fn field_is_quoted() {
         Field {
             name: "zork2-zoog",
             requires_quoting: true,
+            escaped: false,
         },
         field
     );
 }
 
+#[test]
+fn root() {
+    let lookup = Lookup::root();
+    assert!(lookup.is_root());
+    assert!(lookup.is_empty());
+    assert_eq!(lookup, Lookup::default());
+    assert_eq!(lookup.to_string(), "");
+    assert!(!Lookup::from_str("foo").unwrap().is_root());
+}
+
 #[test]
 fn zero_len_not_allowed() {
     let input = "";
@@ -125,6 +137,53 @@ fn array() {
     assert_eq!(lookup.to_string(), input);
 }
 
+#[test]
+fn negative_array() {
+    let input = "foo[-1]";
+    let lookup = Lookup::from_str(input).unwrap();
+    assert_eq!(lookup[0], Segment::from("foo"));
+    assert_eq!(lookup[1], Segment::from(-1));
+    assert_eq!(lookup.to_string(), input);
+}
+
+#[test]
+fn wildcard_array() {
+    let input = "foo[*].bar";
+    let lookup = Lookup::from_str(input).unwrap();
+    assert_eq!(lookup[0], Segment::from("foo"));
+    assert_eq!(lookup[1], Segment::IndexWildcard);
+    assert_eq!(lookup[2], Segment::from("bar"));
+    assert_eq!(lookup.to_string(), input);
+}
+
+#[test]
+fn range_array() {
+    let cases = [
+        ("foo[0..2]", Some(0), Some(2)),
+        ("foo[..2]", None, Some(2)),
+        ("foo[1..]", Some(1), None),
+        ("foo[..]", None, None),
+    ];
+    for (input, start, end) in cases.iter() {
+        let lookup = Lookup::from_str(input).unwrap();
+        assert_eq!(lookup[0], Segment::from("foo"));
+        assert_eq!(
+            lookup[1],
+            Segment::Range {
+                start: *start,
+                end: *end
+            }
+        );
+        assert_eq!(lookup.to_string(), *input);
+    }
+}
+
+#[test]
+fn range_negative_not_allowed() {
+    assert!(Lookup::from_str("foo[-1..]").is_err());
+    assert!(Lookup::from_str("foo[..-1]").is_err());
+}
+
 #[test]
 fn fields() {
     let input = "florp.flop";
@@ -160,6 +219,36 @@ fn coalesced_nesting() {
     assert!(Lookup::from_str(input).is_err());
 }
 
+#[test]
+fn coalesced_within_path() {
+    let input = "snoot.(boop | beep).deeper[0].(a | b | c)";
+    let lookup = Lookup::from_str(input).unwrap();
+    assert_eq!(lookup[0], Segment::from("snoot"));
+    assert_eq!(
+        lookup[1],
+        Segment::from(vec![Field::from("boop"), Field::from("beep")])
+    );
+    assert_eq!(lookup[2], Segment::from("deeper"));
+    assert_eq!(lookup[3], Segment::from(0));
+    assert_eq!(
+        lookup[4],
+        Segment::from(vec![Field::from("a"), Field::from("b"), Field::from("c")])
+    );
+    assert_eq!(lookup.to_string(), input);
+}
+
+#[test]
+fn coalesced_compact() {
+    let lookup = Lookup::from_str("a.(b|c)").unwrap();
+    assert_eq!(lookup[0], Segment::from("a"));
+    assert_eq!(
+        lookup[1],
+        Segment::from(vec![Field::from("b"), Field::from("c")])
+    );
+    assert_eq!(lookup.to_string(), "a.(b | c)");
+    assert_eq!(Lookup::from_str(&lookup.to_string()).unwrap(), lookup);
+}
+
 #[test]
 fn to_string() {
     let input = SUFFICIENTLY_COMPLEX;
@@ -167,6 +256,103 @@ fn to_string() {
     assert_eq!(lookup.to_string(), input);
 }
 
+#[test]
+fn starts_with_and_strip_prefix() {
+    let lookup = Lookup::from_str(SUFFICIENTLY_COMPLEX).unwrap();
+
+    for len in 0..=SUFFICIENTLY_DECOMPOSED.len() {
+        let prefix = Lookup::from(
+            SUFFICIENTLY_DECOMPOSED[..len]
+                .iter()
+                .cloned()
+                .collect::<std::collections::VecDeque<_>>(),
+        );
+        let rest = Lookup::from(
+            SUFFICIENTLY_DECOMPOSED[len..]
+                .iter()
+                .cloned()
+                .collect::<std::collections::VecDeque<_>>(),
+        );
+        assert!(lookup.starts_with(&prefix), "Failed at {}", len);
+        assert_eq!(
+            lookup.strip_prefix(&prefix),
+            Some(rest),
+            "Failed at {}",
+            len
+        );
+    }
+
+    let mut longer = lookup.clone();
+    longer.push_back("extra");
+    assert!(!lookup.starts_with(&longer));
+    assert_eq!(lookup.strip_prefix(&longer), None);
+
+    let other = Lookup::from_str("regular.unquoted").unwrap();
+    assert!(!lookup.starts_with(&other));
+    assert_eq!(lookup.strip_prefix(&other), None);
+}
+
+#[test]
+fn join() {
+    let lookup = Lookup::from_str("a.b").unwrap();
+    let other = Lookup::from_str("c[0]").unwrap();
+
+    let joined = lookup.join(&other);
+    assert_eq!(joined, LookupBuf::from_str("a.b.c[0]").unwrap());
+    assert_eq!(joined.to_string(), "a.b.c[0]");
+
+    let joined = other.join(&lookup);
+    assert_eq!(joined, LookupBuf::from_str("c[0].a.b").unwrap());
+    assert_eq!(joined.to_string(), "c[0].a.b");
+
+    assert_eq!(Lookup::root().join(&other).to_string(), "c[0]");
+    assert_eq!(lookup.join(&Lookup::root()).to_string(), "a.b");
+}
+
+#[test]
+fn parent() {
+    let lookup = Lookup::from_str("foo[0].bar").unwrap();
+    assert_eq!(lookup.parent(), Some(Lookup::from_str("foo[0]").unwrap()));
+
+    let lookup = Lookup::from_str("foo.bar[0]").unwrap();
+    assert_eq!(lookup.parent(), Some(Lookup::from_str("foo.bar").unwrap()));
+
+    let lookup = Lookup::from_str("foo").unwrap();
+    assert_eq!(lookup.parent(), None);
+    assert_eq!(Lookup::root().parent(), None);
+}
+
+#[test]
+fn sorts_structurally() {
+    let mut lookups = vec![
+        "foo[10]",
+        "foo.bar",
+        "foo[2]",
+        "bar",
+        "foo[2].baz",
+        "foo[-1]",
+        "foo",
+    ]
+    .into_iter()
+    .map(|s| Lookup::from_str(s).unwrap())
+    .collect::<Vec<_>>();
+    lookups.sort();
+
+    let sorted = lookups.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        sorted,
+        vec![
+            "bar",
+            "foo",
+            "foo.bar",
+            "foo[-1]",
+            "foo[2]",
+            "foo[2].baz",
+            "foo[10]"
+        ]
+    );
+}
+
 #[test]
 fn impl_index_usize() {
     let lookup = Lookup::from_str(SUFFICIENTLY_COMPLEX).unwrap();
@@ -186,6 +372,105 @@ fn impl_index_mut_index_mut() {
     }
 }
 
+#[test]
+fn slice() {
+    let lookup = Lookup::from_str(SUFFICIENTLY_COMPLEX).unwrap();
+
+    let suffix = lookup.slice(2..);
+    assert_eq!(suffix.len(), SUFFICIENTLY_DECOMPOSED.len() - 2);
+    assert_eq!(
+        suffix.to_string(),
+        r#""quoted but spaces"."quoted.but.periods".lookup[0].00numericstart.nested_lookup[0][0]"#
+    );
+    assert_eq!(
+        lookup.slice(..lookup.len() - 2).to_string(),
+        r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[0].00numericstart.nested_lookup"#
+    );
+    assert_eq!(lookup.slice(4..6).to_string(), "lookup[0]");
+    assert!(lookup.slice(3..3).is_root());
+    assert_eq!(lookup.slice(..), lookup);
+}
+
+#[test]
+fn common_prefix() {
+    let a = Lookup::from_str("a.b.c[0]").unwrap();
+    let b = Lookup::from_str("a.b.d").unwrap();
+    assert_eq!(Lookup::common_prefix(&a, &b).to_string(), "a.b");
+    assert_eq!(Lookup::common_prefix(&b, &a).to_string(), "a.b");
+
+    let prefix = Lookup::from_str("a.b").unwrap();
+    assert_eq!(Lookup::common_prefix(&a, &prefix), prefix);
+    assert_eq!(Lookup::common_prefix(&a, &a), a);
+
+    let unrelated = Lookup::from_str("x.b.c[0]").unwrap();
+    assert!(Lookup::common_prefix(&a, &unrelated).is_root());
+    assert!(Lookup::common_prefix(&a, &Lookup::root()).is_root());
+}
+
+#[test]
+fn to_alphabetical_sort_key() {
+    let mut lookups = vec![
+        Lookup::from_str("foo[10]").unwrap(),
+        Lookup::from_str("foo_bar").unwrap(),
+        Lookup::from_str(r#""foo".baz"#).unwrap(),
+        Lookup::from_str("foo[2]").unwrap(),
+        Lookup::from_str("foo.bar").unwrap(),
+        Lookup::from_str("foo").unwrap(),
+    ];
+    lookups.sort_by_key(|lookup| lookup.to_alphabetical_sort_key());
+
+    assert_eq!(
+        lookups.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec![
+            "foo",
+            "foo.bar",
+            r#""foo".baz"#,
+            "foo[2]",
+            "foo[10]",
+            "foo_bar"
+        ]
+    );
+}
+
+#[test]
+fn borrowed_view_round_trips() {
+    let buf = LookupBuf::from_str(r#""say \"hi\"".x"#).unwrap();
+    let view = buf.to_lookup();
+    assert_eq!(view.to_string(), r#""say \"hi\"".x"#);
+
+    let parsed = Lookup::from_str(r#""say \"hi\"".x"#).unwrap();
+    assert_eq!(Lookup::from_str(&view.to_string()).unwrap(), parsed);
+    assert_eq!(view, parsed);
+    assert_eq!(view.into_buf(), buf);
+    assert_eq!(parsed.into_buf(), buf);
+}
+
+#[test]
+fn borrowed_field_is_not_unescaped_again() {
+    let buf = FieldBuf::from(r#"a\"b"#);
+    let field = Field::from(&buf);
+    assert_eq!(field.as_field_buf(), buf);
+    assert_eq!(field.to_string(), r#""a\\\"b""#);
+}
+
+#[test]
+fn json_pointer_unescapes_quoted_fields() {
+    let lookup = Lookup::from_str(r#"foo."a\"b\\c".bar"#).unwrap();
+    let pointer = lookup.to_json_pointer().unwrap();
+    assert_eq!(pointer, r#"/foo/a"b\c/bar"#);
+
+    let parsed = LookupBuf::from_json_pointer(&pointer).unwrap();
+    assert_eq!(parsed, lookup.into_buf());
+    assert_eq!(parsed.to_json_pointer().unwrap(), pointer);
+}
+
+#[test]
+fn json_pointer_of_borrowed_view() {
+    let buf = LookupBuf::from(SegmentBuf::field(FieldBuf::from(r#"a\"b"#)));
+    assert_eq!(buf.to_json_pointer().unwrap(), r#"/a\"b"#);
+    assert_eq!(buf.to_lookup().to_json_pointer().unwrap(), r#"/a\"b"#);
+}
+
 #[test]
 fn iter() {
     let lookup = Lookup::from_str(SUFFICIENTLY_COMPLEX).unwrap();
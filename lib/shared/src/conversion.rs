@@ -22,11 +22,34 @@ pub enum ConversionError {
 pub enum Conversion {
     Bytes,
     Integer,
+    /// A signed integer in the given radix, which must be between 2 and 36.
+    /// A `0b`, `0o` or `0x` prefix is accepted for radix 2, 8 or 16.
+    IntegerRadix(u32),
     Float,
+    /// A floating point number that may group its integer digits in
+    /// threes with commas, as in `"1,234.5"`. Only comma grouping with a
+    /// `.` decimal point is supported; `"1.234,5"` is rejected.
+    FloatLoose,
     Boolean,
     Timestamp(TimeZone),
     TimestampFmt(String, TimeZone),
     TimestampTzFmt(String),
+    /// Tries each format in order and uses the first that parses. Formats
+    /// without a time zone are interpreted in the given `TimeZone`.
+    TimestampFmts(Vec<String>, TimeZone),
+    /// Encodes the bytes as base64 text using the given alphabet, with padding.
+    Base64(Base64Alphabet),
+    /// Encodes the bytes as lowercase hexadecimal text.
+    Hex,
+}
+
+/// The alphabet used by `Conversion::Base64`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Base64Alphabet {
+    /// The standard alphabet from RFC 4648, using `+` and `/`.
+    Standard,
+    /// The URL and filename safe alphabet from RFC 4648, using `-` and `_`.
+    UrlSafe,
 }
 
 #[derive(Debug, Eq, PartialEq, Snafu)]
@@ -35,8 +58,12 @@ pub enum Error {
     BoolParseError { s: String },
     #[snafu(display("Invalid integer {:?}: {}", s, source))]
     IntParseError { s: String, source: ParseIntError },
+    #[snafu(display("Invalid integer radix {}, must be between 2 and 36", radix))]
+    InvalidRadix { radix: u32 },
     #[snafu(display("Invalid floating point number {:?}: {}", s, source))]
     FloatParseError { s: String, source: ParseFloatError },
+    #[snafu(display("Invalid digit grouping in floating point number {:?}", s))]
+    FloatGroupingError { s: String },
     #[snafu(
         display("Invalid timestamp {:?}: {}", s, source),
         visibility(pub(super))
@@ -44,6 +71,8 @@ pub enum Error {
     TimestampParseError { s: String, source: ChronoParseError },
     #[snafu(display("No matching timestamp format found for {:?}", s))]
     AutoTimestampParseError { s: String },
+    #[snafu(display("None of the timestamp formats {:?} matched {:?}", formats, s))]
+    TimestampFmtsParseError { s: String, formats: Vec<String> },
 }
 
 /// Helper function to parse a conversion map and check against a list of names
@@ -84,17 +113,24 @@ impl Conversion {
     ///  * `"asis"`, `"bytes"`, or `"string"` => As-is (no conversion)
     ///  * `"int"` or `"integer"` => Signed integer
     ///  * `"float"` => Floating point number
+    ///  * `"float_loose"` => Floating point number with optional `,` digit grouping
     ///  * `"bool"` or `"boolean"` => Boolean
     ///  * `"timestamp"` => Timestamp, guessed using a set of formats
     ///  * `"timestamp|FORMAT"` => Timestamp using the given format
+    ///  * `"base64"` or `"base64_url_safe"` => Base64 encoded text
+    ///  * `"hex"` => Hexadecimal encoded text
     pub fn parse(s: impl AsRef<str>, tz: TimeZone) -> Result<Self, ConversionError> {
         let s = s.as_ref();
         match s {
             "asis" | "bytes" | "string" => Ok(Self::Bytes),
             "integer" | "int" => Ok(Self::Integer),
             "float" => Ok(Self::Float),
+            "float_loose" => Ok(Self::FloatLoose),
             "bool" | "boolean" => Ok(Self::Boolean),
             "timestamp" => Ok(Self::Timestamp(tz)),
+            "base64" => Ok(Self::Base64(Base64Alphabet::Standard)),
+            "base64_url_safe" => Ok(Self::Base64(Base64Alphabet::UrlSafe)),
+            "hex" => Ok(Self::Hex),
             _ if s.starts_with("timestamp|") => {
                 let fmt = &s[10..];
                 // DateTime<Utc> can only convert timestamps without
@@ -124,12 +160,16 @@ impl Conversion {
                     .with_context(|| IntParseError { s })?
                     .into()
             }
+            Self::IntegerRadix(radix) => {
+                parse_int_radix(&String::from_utf8_lossy(&bytes), *radix)?.into()
+            }
             Self::Float => {
                 let s = String::from_utf8_lossy(&bytes);
                 s.parse::<f64>()
                     .with_context(|| FloatParseError { s })?
                     .into()
             }
+            Self::FloatLoose => parse_float_loose(&String::from_utf8_lossy(&bytes))?.into(),
             Self::Boolean => parse_bool(&String::from_utf8_lossy(&bytes))?.into(),
             Self::Timestamp(tz) => parse_timestamp(*tz, &String::from_utf8_lossy(&bytes))?.into(),
             Self::TimestampFmt(format, tz) => {
@@ -147,8 +187,72 @@ impl Conversion {
 
                 datetime_to_utc(dt).into()
             }
+            Self::TimestampFmts(formats, tz) => {
+                parse_timestamp_fmts(*tz, formats, &String::from_utf8_lossy(&bytes))?.into()
+            }
+            Self::Base64(alphabet) => {
+                let config = match alphabet {
+                    Base64Alphabet::Standard => base64::STANDARD,
+                    Base64Alphabet::UrlSafe => base64::URL_SAFE,
+                };
+                Bytes::from(base64::encode_config(&bytes, config)).into()
+            }
+            Self::Hex => Bytes::from(hex::encode(&bytes)).into(),
+        })
+    }
+}
+
+/// Parse a string into an `i64` in the given radix, dropping the
+/// conventional `0b`, `0o` or `0x` prefix if it matches the radix.
+fn parse_int_radix(s: &str, radix: u32) -> Result<i64, Error> {
+    if !(2..=36).contains(&radix) {
+        return Err(Error::InvalidRadix { radix });
+    }
+
+    let prefix = match radix {
+        2 => Some("0b"),
+        8 => Some("0o"),
+        16 => Some("0x"),
+        _ => None,
+    };
+    let digits = prefix
+        .filter(|prefix| {
+            s.get(..2)
+                .map_or(false, |head| head.eq_ignore_ascii_case(prefix))
         })
+        .map_or(s, |_| &s[2..]);
+
+    i64::from_str_radix(digits, radix).with_context(|| IntParseError { s })
+}
+
+/// Parse a string into an `f64`, allowing the integer digits to be
+/// grouped in threes with commas. The commas are only stripped when every
+/// group after the first has exactly three digits and none appear after the
+/// decimal point, so European style `"1.234,5"` is an error rather than
+/// being read as `1.2345`.
+fn parse_float_loose(s: &str) -> Result<f64, Error> {
+    let (integer, fraction) = match s.find(|c| c == '.' || c == 'e' || c == 'E') {
+        Some(index) => s.split_at(index),
+        None => (s, ""),
+    };
+    if !integer.contains(',') {
+        return s.parse::<f64>().with_context(|| FloatParseError { s });
     }
+
+    let digits = integer.trim_start_matches(|c| c == '+' || c == '-');
+    let mut groups = digits.split(',');
+    let first = groups.next().unwrap_or_default();
+    let grouped = (1..=3).contains(&first.len())
+        && groups.all(|group| group.len() == 3)
+        && !fraction.contains(',');
+    if !grouped {
+        return Err(Error::FloatGroupingError { s: s.into() });
+    }
+
+    let stripped = s.replace(',', "");
+    stripped
+        .parse::<f64>()
+        .with_context(|| FloatParseError { s })
 }
 
 /// Parse a string into a native `bool`. The built in `bool::from_str`
@@ -156,17 +260,17 @@ impl Conversion {
 /// to convert from a more diverse set of strings. In particular, the
 /// following set of source strings are allowed:
 ///
-///  * `"true"`, `"t"`, `"yes"`, `"y"` (all case-insensitive), and
+///  * `"true"`, `"t"`, `"yes"`, `"y"`, `"on"` (all case-insensitive), and
 ///  non-zero integers all convert to `true`.
 ///
-///  * `"false"`, `"f"`, `"no"`, `"n"` (all case-insensitive), and `"0"`
-///  all convert to `false`.
+///  * `"false"`, `"f"`, `"no"`, `"n"`, `"off"` (all case-insensitive), and
+///  `"0"` all convert to `false`.
 ///
 /// Anything else results in a parse error.
 fn parse_bool(s: &str) -> Result<bool, Error> {
     match s {
-        "true" | "t" | "yes" | "y" => Ok(true),
-        "false" | "f" | "no" | "n" | "0" => Ok(false),
+        "true" | "t" | "yes" | "y" | "on" => Ok(true),
+        "false" | "f" | "no" | "n" | "off" | "0" => Ok(false),
         _ => {
             if let Ok(n) = s.parse::<isize>() {
                 Ok(n != 0)
@@ -174,8 +278,8 @@ fn parse_bool(s: &str) -> Result<bool, Error> {
                 // Do the case conversion only if simple matches fail,
                 // since this operation can be expensive.
                 match s.to_lowercase().as_str() {
-                    "true" | "t" | "yes" | "y" => Ok(true),
-                    "false" | "f" | "no" | "n" => Ok(false),
+                    "true" | "t" | "yes" | "y" | "on" => Ok(true),
+                    "false" | "f" | "no" | "n" | "off" => Ok(false),
                     _ => Err(Error::BoolParseError { s: s.into() }),
                 }
             }
@@ -183,6 +287,24 @@ fn parse_bool(s: &str) -> Result<bool, Error> {
     }
 }
 
+/// Parse a string into a timestamp using the first of `formats` that matches.
+fn parse_timestamp_fmts(tz: TimeZone, formats: &[String], s: &str) -> Result<DateTime<Utc>, Error> {
+    for format in formats {
+        let result = if format_has_zone(format) {
+            DateTime::parse_from_str(s, format).map(datetime_to_utc)
+        } else {
+            tz.datetime_from_str(s, format)
+        };
+        if let Ok(result) = result {
+            return Ok(result);
+        }
+    }
+    Err(Error::TimestampFmtsParseError {
+        s: s.into(),
+        formats: formats.to_vec(),
+    })
+}
+
 /// Does the format specifier have a time zone option?
 fn format_has_zone(fmt: &str) -> bool {
     fmt.contains("%Z")
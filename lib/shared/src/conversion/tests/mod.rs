@@ -1,4 +1,7 @@
-use crate::conversion::parse_bool;
+use crate::conversion::{
+    parse_bool, parse_float_loose, parse_int_radix, Base64Alphabet, Conversion, Error,
+};
+use crate::datetime::TimeZone;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 
@@ -58,6 +61,8 @@ fn parse_bool_true() {
     assert_eq!(parse_bool("YES"), Ok(true));
     assert_eq!(parse_bool("y"), Ok(true));
     assert_eq!(parse_bool("Y"), Ok(true));
+    assert_eq!(parse_bool("on"), Ok(true));
+    assert_eq!(parse_bool("On"), Ok(true));
     assert_eq!(parse_bool("1"), Ok(true));
     assert_eq!(parse_bool("23456"), Ok(true));
     assert_eq!(parse_bool("-8"), Ok(true));
@@ -73,6 +78,8 @@ fn parse_bool_false() {
     assert_eq!(parse_bool("NO"), Ok(false));
     assert_eq!(parse_bool("n"), Ok(false));
     assert_eq!(parse_bool("N"), Ok(false));
+    assert_eq!(parse_bool("off"), Ok(false));
+    assert_eq!(parse_bool("OFF"), Ok(false));
     assert_eq!(parse_bool("0"), Ok(false));
     assert_eq!(parse_bool("000"), Ok(false));
 }
@@ -82,4 +89,97 @@ fn parse_bool_errors() {
     assert!(parse_bool("X").is_err());
     assert!(parse_bool("yes or no").is_err());
     assert!(parse_bool("123.4").is_err());
+    assert!(parse_bool("o").is_err());
+    assert_eq!(
+        parse_bool("maybe"),
+        Err(Error::BoolParseError { s: "maybe".into() })
+    );
+}
+
+#[test]
+fn parse_int_radix_ok() {
+    assert_eq!(parse_int_radix("ff", 16), Ok(255));
+    assert_eq!(parse_int_radix("FF", 16), Ok(255));
+    assert_eq!(parse_int_radix("0xff", 16), Ok(255));
+    assert_eq!(parse_int_radix("0XFF", 16), Ok(255));
+    assert_eq!(parse_int_radix("-ff", 16), Ok(-255));
+    assert_eq!(parse_int_radix("0b101", 2), Ok(5));
+    assert_eq!(parse_int_radix("0o17", 8), Ok(15));
+    assert_eq!(parse_int_radix("z", 36), Ok(35));
+}
+
+#[test]
+fn parse_int_radix_errors() {
+    assert!(parse_int_radix("zz", 16).is_err());
+    // The prefix is only dropped when it matches the radix.
+    assert!(parse_int_radix("0x10", 8).is_err());
+    assert_eq!(
+        parse_int_radix("10", 37),
+        Err(Error::InvalidRadix { radix: 37 })
+    );
+}
+
+#[test]
+fn parse_float_loose_ok() {
+    assert_eq!(parse_float_loose("1,234.5"), Ok(1234.5));
+    assert_eq!(parse_float_loose("-12,345,678"), Ok(-12345678.0));
+    assert_eq!(parse_float_loose("1,234e3"), Ok(1234000.0));
+    assert_eq!(parse_float_loose("1234.5"), Ok(1234.5));
+}
+
+#[test]
+fn parse_float_loose_errors() {
+    assert!(parse_float_loose("1.2.3").is_err());
+    assert!(parse_float_loose("1.234,5").is_err());
+    assert!(parse_float_loose("12,34.5").is_err());
+    assert!(parse_float_loose("1234,567.8").is_err());
+    assert!(parse_float_loose(",123").is_err());
+}
+
+#[test]
+fn float_rejects_grouping() {
+    let result = Conversion::parse("float", TimeZone::Local)
+        .unwrap()
+        .convert::<StubValue>(Bytes::from("1,234.5"));
+    assert!(matches!(result, Err(Error::FloatParseError { .. })));
+}
+
+fn encode(conversion: &str, input: &[u8]) -> Bytes {
+    match Conversion::parse(conversion, TimeZone::Local)
+        .unwrap()
+        .convert::<StubValue>(Bytes::copy_from_slice(input))
+    {
+        Ok(StubValue::Bytes(bytes)) => bytes,
+        other => panic!("Expected bytes, got {:?}", other),
+    }
+}
+
+#[test]
+fn base64_round_trip() {
+    let input = b"\x00\xfb\xff binary \xfe";
+
+    let standard = encode("base64", input);
+    assert_eq!(standard, "APv/IGJpbmFyeSD+");
+    assert_eq!(base64::decode(&standard).unwrap(), input);
+
+    let url_safe = encode("base64_url_safe", input);
+    assert_eq!(url_safe, "APv_IGJpbmFyeSD-");
+    assert_eq!(
+        base64::decode_config(&url_safe, base64::URL_SAFE).unwrap(),
+        input
+    );
+
+    assert!(matches!(
+        Conversion::parse("base64", TimeZone::Local),
+        Ok(Conversion::Base64(Base64Alphabet::Standard))
+    ));
+}
+
+#[test]
+fn hex_round_trip() {
+    let input = b"\x00\xfb\xff binary \xfe";
+
+    let encoded = encode("hex", input);
+    assert_eq!(encoded, "00fbff2062696e61727920fe");
+    assert_eq!(hex::decode(&encoded).unwrap(), input);
 }
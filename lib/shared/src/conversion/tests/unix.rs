@@ -69,3 +69,28 @@ fn timestamp_conversion() {
         Ok(dateref().into())
     );
 }
+
+#[test]
+fn timestamp_fmts_conversion() {
+    std::env::set_var("TZ", TIMEZONE_NAME);
+    let conversion = Conversion::TimestampFmts(
+        vec!["%+".into(), "%Y/%m/%d %H:%M:%S".into()],
+        TimeZone::Local,
+    );
+
+    assert_eq!(
+        conversion.convert::<StubValue>("2001-02-03T04:05:06Z".into()),
+        Ok(dateref().into())
+    );
+    assert_eq!(
+        conversion.convert::<StubValue>("2001/02/03 14:05:06".into()),
+        Ok(dateref().into())
+    );
+    assert_eq!(
+        conversion.convert::<StubValue>("03.02.2001".into()),
+        Err(Error::TimestampFmtsParseError {
+            s: "03.02.2001".into(),
+            formats: vec!["%+".into(), "%Y/%m/%d %H:%M:%S".into()],
+        })
+    );
+}
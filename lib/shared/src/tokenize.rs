@@ -1,42 +1,126 @@
+use std::borrow::Cow;
+
 use nom::{
     branch::alt,
-    bytes::complete::{escaped, is_not, tag},
+    bytes::complete::{escaped, is_not, tag, take_while},
     character::complete::{one_of, space0},
     combinator::{all_consuming, map, opt, rest, verify},
     error::ErrorKind,
     multi::many0,
-    sequence::{delimited, terminated},
+    sequence::{delimited, preceded, separated_pair, terminated},
+    IResult,
 };
 
-pub fn parse(input: &str) -> Vec<&str> {
-    let simple = is_not::<_, _, (&str, ErrorKind)>(" \t[\"");
-    let string = delimited(
-        tag("\""),
-        map(opt(escaped(is_not("\"\\"), '\\', one_of("\"\\"))), |o| {
-            o.unwrap_or("")
-        }),
-        tag("\""),
+/// Split `input` into whitespace separated tokens. Text wrapped in double
+/// quotes, square brackets or curly braces is kept together as a single token without the
+/// wrapping characters, and backslash escapes inside quotes are removed.
+pub fn parse(input: &str) -> Vec<Cow<'_, str>> {
+    parse_with(input, &[' ', '\t'])
+}
+
+/// Split `input` into tokens like `parse`, but separated by any of the
+/// given `delimiters` instead of whitespace. Runs of delimiters are treated
+/// as a single separator.
+pub fn parse_with<'a>(input: &'a str, delimiters: &[char]) -> Vec<Cow<'a, str>> {
+    let stop = delimiters
+        .iter()
+        .chain(&['[', '{', '"'])
+        .collect::<String>();
+    let simple = map(
+        is_not::<_, _, (&str, ErrorKind)>(stop.as_str()),
+        Cow::Borrowed,
     );
-    let bracket = delimited(
-        tag("["),
-        map(opt(escaped(is_not("]\\"), '\\', one_of("]\\"))), |o| {
-            o.unwrap_or("")
-        }),
-        tag("]"),
+    let bracket = map(
+        delimited(
+            tag("["),
+            map(opt(escaped(is_not("]\\"), '\\', one_of("]\\"))), |o| {
+                o.unwrap_or("")
+            }),
+            tag("]"),
+        ),
+        Cow::Borrowed,
+    );
+    let brace = map(
+        delimited(
+            tag("{"),
+            map(opt(escaped(is_not("}\\"), '\\', one_of("}\\"))), |o| {
+                o.unwrap_or("")
+            }),
+            tag("}"),
+        ),
+        Cow::Borrowed,
     );
 
     // fall back to returning the rest of the input, if any
-    let remainder = verify(rest, |s: &str| !s.is_empty());
-    let field = alt((bracket, string, simple, remainder));
+    let remainder = map(verify(rest, |s: &str| !s.is_empty()), Cow::Borrowed);
+    let field = alt((bracket, brace, quoted, simple, remainder));
+
+    let separator = take_while(|c| delimiters.contains(&c));
+    let mut parser = all_consuming(many0(terminated(field, separator)));
 
-    all_consuming(many0(terminated(field, space0)))(input)
+    parser(input).expect("parser should always succeed").1
+}
+
+/// Split logfmt style `key=value` pairs out of `input`. Values may be
+/// double quoted to include whitespace, in which case they are unescaped as
+/// in `parse`. A key followed by `=` and nothing else has an empty value.
+/// Tokens without an `=` are skipped.
+pub fn parse_kv(input: &str) -> Vec<(String, String)> {
+    let key = is_not::<_, _, (&str, ErrorKind)>(" \t=\"");
+    let value = alt((
+        quoted,
+        map(opt(is_not(" \t")), |o| Cow::Borrowed(o.unwrap_or(""))),
+    ));
+    let pair = map(separated_pair(key, tag("="), value), |(key, value)| {
+        Some((key.to_owned(), value.into_owned()))
+    });
+    let skipped = map(is_not(" \t"), |_| None);
+    let field = alt((pair, skipped));
+
+    let mut parser = all_consuming(terminated(many0(preceded(space0, field)), space0));
+
+    parser(input)
         .expect("parser should always succeed")
         .1
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Parse a double quoted token, unescaping its contents.
+fn quoted(input: &str) -> IResult<&str, Cow<'_, str>, (&str, ErrorKind)> {
+    map(
+        delimited(
+            tag("\""),
+            map(opt(escaped(is_not("\"\\"), '\\', one_of("\"\\"))), |o| {
+                o.unwrap_or("")
+            }),
+            tag("\""),
+        ),
+        unescape,
+    )(input)
+}
+
+/// Drop the backslash from each escaped character in a quoted token.
+fn unescape(s: &str) -> Cow<'_, str> {
+    if !s.contains('\\') {
+        return Cow::Borrowed(s);
+    }
+
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    Cow::Owned(unescaped)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::parse;
+    use super::{parse, parse_kv, parse_with};
 
     #[test]
     fn basic() {
@@ -70,9 +154,15 @@ mod tests {
 
     #[test]
     fn escaped_quotes() {
+        assert_eq!(parse(r#"foo "bar \" \" baz""#), &["foo", r#"bar " " baz"#],);
+        assert_eq!(parse(r#""back\\slash""#), &[r#"back\slash"#]);
+    }
+
+    #[test]
+    fn mixed_escaped_quotes() {
         assert_eq!(
-            parse(r#"foo "bar \" \" baz""#),
-            &["foo", r#"bar \" \" baz"#],
+            parse(r#"GET "a \"b\" c" 200 "-""#),
+            &["GET", r#"a "b" c"#, "200", "-"],
         );
     }
 
@@ -104,6 +194,30 @@ mod tests {
         assert_eq!(parse("foo [bar"), &["foo", "[bar"],);
     }
 
+    #[test]
+    fn bracketed_datetime() {
+        assert_eq!(
+            parse("[2021-01-01 12:00:00] foo"),
+            &["2021-01-01 12:00:00", "foo"]
+        );
+    }
+
+    #[test]
+    fn braces() {
+        assert_eq!(
+            parse(r#"{"a": 1, "b": [2]} quux"#),
+            &[r#""a": 1, "b": [2]"#, "quux"]
+        );
+        assert_eq!(parse(r#"{foo \} bar} baz"#), &[r#"foo \} bar"#, "baz"]);
+    }
+
+    #[test]
+    fn unclosed_braces() {
+        assert_eq!(parse("foo {bar"), &["foo", "{bar"]);
+        assert_eq!(parse("foo [bar} baz"), &["foo", "[bar} baz"]);
+        assert_eq!(parse("foo {bar] baz"), &["foo", "{bar] baz"]);
+    }
+
     #[test]
     fn truncated_field() {
         assert_eq!(
@@ -118,6 +232,37 @@ mod tests {
         assert_eq!(parse("foo - bar"), &["foo", "-", "bar"]);
     }
 
+    #[test]
+    fn custom_delimiters() {
+        assert_eq!(parse_with("a,b;;c", &[',', ';']), &["a", "b", "c"]);
+        assert_eq!(
+            parse_with(r#"a b,"c;d",[e,f]"#, &[',']),
+            &["a b", "c;d", "e,f"]
+        );
+    }
+
+    #[test]
+    fn key_values() {
+        assert_eq!(
+            parse_kv(r#"level=info msg="value two" path=/a empty= bare"#),
+            vec![
+                ("level".to_string(), "info".to_string()),
+                ("msg".to_string(), "value two".to_string()),
+                ("path".to_string(), "/a".to_string()),
+                ("empty".to_string(), "".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_kv(r#"  a="say \"hi\"" b="unclosed c=d  "#),
+            vec![
+                ("a".to_string(), r#"say "hi""#.to_string()),
+                ("b".to_string(), "\"unclosed".to_string()),
+                ("c".to_string(), "d".to_string()),
+            ]
+        );
+        assert!(parse_kv("").is_empty());
+    }
+
     #[test]
     fn from_fuzzing() {
         assert_eq!(parse("").len(), 0);
@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use vector_core::event::{discriminant::Discriminant, LogEvent};
+
+const FIELDS: &[&str] = &["hostname", "service", "region", "request_id", "user"];
+
+fn event() -> LogEvent {
+    let mut event = LogEvent::from("a fairly ordinary log message");
+    for field in FIELDS {
+        event.insert(*field, format!("{} value", field));
+    }
+    event.insert("nested.a", "a");
+    event.insert("nested.b", 1);
+    event
+}
+
+fn from_log_event(c: &mut Criterion) {
+    let mut group = c.benchmark_group("discriminant/from_log_event");
+    group.throughput(Throughput::Elements(1));
+
+    // Up to four fields are stored inline, the fifth spills to the heap.
+    for count in 1..=FIELDS.len() {
+        let fields = &FIELDS[..count];
+        group.bench_with_input(BenchmarkId::new("scalar", count), fields, |b, fields| {
+            b.iter_batched_ref(
+                event,
+                |event| Discriminant::from_log_event(event, fields),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.bench_function("map", |b| {
+        b.iter_batched_ref(
+            event,
+            |event| Discriminant::from_log_event(event, &["nested"]),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    name = benches;
+    // encapsulates CI noise we saw in
+    // https://github.com/timberio/vector/issues/5394
+    config = Criterion::default().noise_threshold(0.05);
+    targets = from_log_event
+);
+criterion_main!(benches);
@@ -1,4 +1,5 @@
 use super::{LogEvent, Value};
+use smallvec::SmallVec;
 use std::{
     collections::BTreeMap,
     hash::{Hash, Hasher},
@@ -15,21 +16,36 @@ use std::{
 /// Intended for dissecting streams of events to sub-streams, for instance to
 /// be able to allocate a buffer per sub-stream.
 /// Implements `PartialEq`, `Eq` and `Hash` to enable use as a `HashMap` key.
+///
+/// The values are stored inline for up to four fields, and cloning a
+/// `Value::Bytes` shares its buffer rather than copying it, so building a
+/// discriminant from a few scalar fields avoids per-event allocations. The
+/// full values are kept rather than a hash of them so that distinct groups
+/// can never collide.
 #[derive(Debug, Clone)]
 pub struct Discriminant {
-    values: Vec<Option<Value>>,
+    values: SmallVec<[Option<Value>; 4]>,
 }
 
 impl Discriminant {
     /// Create a new Discriminant from the `LogEvent` and an ordered slice of
     /// fields to include into a discriminant value.
     pub fn from_log_event(event: &LogEvent, discriminant_fields: &[impl AsRef<str>]) -> Self {
-        let values: Vec<Option<Value>> = discriminant_fields
+        let values = discriminant_fields
             .iter()
             .map(|discriminant_field| event.get(discriminant_field).cloned())
             .collect();
         Self { values }
     }
+
+    /// Create a new Discriminant from a single value that was derived from
+    /// the event, for instance by rendering a template. `None` stands for a
+    /// value that couldn't be derived.
+    pub fn from_value(value: Option<Value>) -> Self {
+        let mut values = SmallVec::new();
+        values.push(value);
+        Self { values }
+    }
 }
 
 impl PartialEq for Discriminant {
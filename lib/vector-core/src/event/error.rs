@@ -20,6 +20,8 @@ pub enum EventError {
     EmptyCoalesceSubSegment,
     #[snafu(display("Cannot remove self."))]
     RemovingSelf,
+    #[snafu(display("Cannot insert into a wildcard or range index."))]
+    AmbiguousInsert,
 }
 
 impl From<lookup::LookupError> for EventError {
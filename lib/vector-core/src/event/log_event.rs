@@ -155,6 +155,12 @@ impl LogEvent {
         util::log::all_fields(self.as_map())
     }
 
+    /// Iterates over all leaf fields as structured lookups, in the same order as `all_fields`.
+    #[instrument(level = "trace", skip(self))]
+    pub fn pairs(&self) -> impl Iterator<Item = (lookup::Lookup<'_>, &Value)> {
+        util::log::pairs(self.as_map())
+    }
+
     #[instrument(level = "trace", skip(self))]
     pub fn is_empty(&self) -> bool {
         self.as_map().is_empty()
@@ -232,6 +238,23 @@ impl LogEvent {
     }
 }
 
+impl LogEvent {
+    /// Tests if `self` and `other` contain the same data, as `event_data_eq`
+    /// does, apart from the values at `paths`. This lets tests compare events
+    /// with values that differ between runs, such as timestamps.
+    pub fn eq_ignoring(&self, other: &LogEvent, paths: &[lookup::Lookup<'_>]) -> bool {
+        let without_paths = |fields: &Value| {
+            let mut fields = fields.clone();
+            for path in paths {
+                // A path that can't be removed has nothing to ignore.
+                let _ = fields.remove(path.clone(), false);
+            }
+            fields
+        };
+        without_paths(&self.fields) == without_paths(&other.fields)
+    }
+}
+
 impl EventDataEq for LogEvent {
     fn event_data_eq(&self, other: &Self) -> bool {
         self.fields == other.fields && self.metadata.event_data_eq(&other.metadata)
@@ -357,6 +380,15 @@ impl<K: AsRef<str>, V: Into<Value>> FromIterator<(K, V)> for LogEvent {
     }
 }
 
+/// Serializes the fields of `log` into a single level JSON object, keyed by
+/// the path of each leaf in alphabetical order, such as
+/// `{"a.b":1,"a.c[0]":true}`. Logs with the same fields give the same string
+/// whatever order the fields were inserted in, which suits snapshot tests and
+/// deduplicating by content.
+pub fn log_event_to_sorted_json(log: &LogEvent) -> String {
+    serde_json::to_string(&log.all_fields()).expect("Cannot serialize fields")
+}
+
 impl Serialize for LogEvent {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -545,4 +577,26 @@ mod test {
 
         shared::assert_event_data_eq!(merged, expected);
     }
+
+    #[test]
+    fn sorted_json_ignores_insertion_order() {
+        let mut first = LogEvent::default();
+        first.insert("message", "hello");
+        first.insert("nested.b", 2);
+        first.insert("nested.a[0]", true);
+        first.insert("count", 1.5);
+
+        let mut second = LogEvent::default();
+        second.insert("nested.a[0]", true);
+        second.insert("count", 1.5);
+        second.insert("nested.b", 2);
+        second.insert("message", "hello");
+
+        let json = log_event_to_sorted_json(&first);
+        assert_eq!(
+            json,
+            r#"{"count":1.5,"message":"hello","nested.a[0]":true,"nested.b":2}"#
+        );
+        assert_eq!(json, log_event_to_sorted_json(&second));
+    }
 }
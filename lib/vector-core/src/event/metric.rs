@@ -584,6 +584,19 @@ impl AsRef<MetricData> for MetricData {
 }
 
 impl MetricValue {
+    /// Returns the name of this value's type, as used when serializing the
+    /// metric.
+    pub fn as_name(&self) -> &'static str {
+        match self {
+            Self::Counter { .. } => "counter",
+            Self::Gauge { .. } => "gauge",
+            Self::Set { .. } => "set",
+            Self::Distribution { .. } => "distribution",
+            Self::AggregatedHistogram { .. } => "aggregated_histogram",
+            Self::AggregatedSummary { .. } => "aggregated_summary",
+        }
+    }
+
     /// Zero out all the values contained in this. This keeps all the
     /// bucket/value vectors for the histogram and summary metric types
     /// intact while zeroing the counts. Distribution metrics are
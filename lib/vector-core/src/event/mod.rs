@@ -5,7 +5,7 @@ pub use finalization::{
     BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
 };
 pub use legacy_lookup::Lookup;
-pub use log_event::LogEvent;
+pub use log_event::{log_event_to_sorted_json, LogEvent};
 pub use metadata::{EventMetadata, WithMetadata};
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
 use prost::{DecodeError, EncodeError, Message};
@@ -89,6 +89,14 @@ impl Event {
         }
     }
 
+    /// Coerces self into a `LogEvent`, or gives self back if it is not an `Event::Log`.
+    pub fn try_into_log(self) -> Result<LogEvent, Event> {
+        match self {
+            Event::Log(log) => Ok(log),
+            _ => Err(self),
+        }
+    }
+
     /// Return self as a `Metric`
     ///
     /// # Panics
@@ -125,6 +133,14 @@ impl Event {
         }
     }
 
+    /// Coerces self into a `Metric`, or gives self back if it is not an `Event::Metric`.
+    pub fn try_into_metric(self) -> Result<Metric, Event> {
+        match self {
+            Event::Metric(metric) => Ok(metric),
+            _ => Err(self),
+        }
+    }
+
     pub fn metadata(&self) -> &EventMetadata {
         match self {
             Self::Log(log) => log.metadata(),
@@ -163,6 +179,18 @@ impl Event {
     }
 }
 
+impl Event {
+    /// Tests if `self` and `other` contain the same data apart from the
+    /// values at `paths`, see `LogEvent::eq_ignoring`. Metrics are compared
+    /// with `event_data_eq`, ignoring `paths`.
+    pub fn eq_ignoring(&self, other: &Event, paths: &[lookup::Lookup<'_>]) -> bool {
+        match (self, other) {
+            (Self::Log(a), Self::Log(b)) => a.eq_ignoring(b, paths),
+            _ => self.event_data_eq(other),
+        }
+    }
+}
+
 impl EventDataEq for Event {
     fn event_data_eq(&self, other: &Self) -> bool {
         match (self, other) {
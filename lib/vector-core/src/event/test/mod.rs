@@ -2,6 +2,7 @@ mod common;
 mod serialization;
 
 use super::*;
+use chrono::{TimeZone, Utc};
 use std::collections::HashSet;
 
 #[test]
@@ -55,3 +56,45 @@ fn event_iteration_order() {
         ]
     );
 }
+
+#[test]
+fn event_eq_ignoring() {
+    let timestamp_key = crate::config::log_schema().timestamp_key();
+    let timestamp = lookup::Lookup::from(timestamp_key);
+
+    let mut a = Event::from("same message");
+    let mut b = Event::from("same message");
+    a.as_mut_log()
+        .insert(timestamp_key, Utc.ymd(2021, 7, 1).and_hms(12, 0, 0));
+    b.as_mut_log()
+        .insert(timestamp_key, Utc.ymd(2021, 7, 1).and_hms(12, 0, 1));
+    assert_ne!(a, b);
+    assert!(a.eq_ignoring(&b, &[timestamp.clone()]));
+
+    // Nested paths are ignored, and anything else still has to match.
+    a.as_mut_log().insert("nested.id", 1);
+    b.as_mut_log().insert("nested.id", 2);
+    assert!(!a.eq_ignoring(&b, &[timestamp.clone()]));
+    let nested_id = lookup::Lookup::from_str("nested.id").unwrap();
+    assert!(a.eq_ignoring(&b, &[timestamp.clone(), nested_id]));
+
+    let c = Event::from("another message");
+    assert!(!a.eq_ignoring(&c, &[timestamp]));
+}
+
+#[test]
+fn event_try_into() {
+    let mut log = Event::new_empty_log();
+    log.as_mut_log().insert("message", "not a metric");
+    let metric = Event::Metric(Metric::new(
+        "counter",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    ));
+
+    assert_eq!(log.clone().try_into_metric(), Err(log.clone()));
+    assert_eq!(metric.clone().try_into_log(), Err(metric.clone()));
+
+    assert_eq!(log.clone().try_into_log(), Ok(log.into_log()));
+    assert_eq!(metric.clone().try_into_metric(), Ok(metric.into_metric()));
+}
@@ -1,7 +1,8 @@
 use super::Value;
+use lookup::{quote_field, Lookup, Segment};
 use serde::{Serialize, Serializer};
 use std::{
-    collections::{btree_map, BTreeMap},
+    collections::{btree_map, BTreeMap, VecDeque},
     iter, slice,
 };
 
@@ -13,6 +14,40 @@ pub fn all_fields(
     FieldsIter::new(fields)
 }
 
+/// Iterates over all paths under `prefix` in alphabetical order and their
+/// corresponding values, without visiting the rest of the fields.
+///
+/// Only field and index segments are supported in `prefix`. If nothing exists
+/// at `prefix` the iterator is empty.
+pub fn fields_under<'a>(
+    fields: &'a BTreeMap<String, Value>,
+    prefix: &Lookup<'_>,
+) -> Box<dyn Iterator<Item = (String, &'a Value)> + 'a> {
+    if !prefix
+        .iter()
+        .all(|segment| segment.is_field() || segment.is_index())
+    {
+        return Box::new(iter::empty());
+    }
+
+    let mut segments = prefix.iter().cloned().collect::<VecDeque<_>>();
+    let value = match segments.pop_front() {
+        None => return Box::new(FieldsIter::new(fields)),
+        Some(Segment::Field(field)) => fields
+            .get(field.name)
+            .and_then(|value| value.get(Lookup::from(segments)).ok().flatten()),
+        Some(_) => None,
+    };
+
+    match value {
+        Some(value @ Value::Map(_)) | Some(value @ Value::Array(_)) => {
+            Box::new(FieldsIter::nested(value, prefix.to_string()))
+        }
+        Some(value) => Box::new(iter::once((prefix.to_string(), value))),
+        None => Box::new(iter::empty()),
+    }
+}
+
 #[derive(Clone)]
 enum LeafIter<'a> {
     Map(btree_map::Iter<'a, String, Value>),
@@ -20,69 +55,199 @@ enum LeafIter<'a> {
 }
 
 #[derive(Clone, Copy)]
-enum PathComponent<'a> {
+pub(super) enum PathComponent<'a> {
     Key(&'a String),
     Index(usize),
 }
 
+/// How the components of a path yielded by `FieldsIter` are joined together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathStyle {
+    /// `a.b[0].c`, the form understood by the lookup parser and `PathIter`.
+    DottedBracket,
+    /// `a/b/0/c`, with slashes in keys escaped as `\/`.
+    Slash,
+    /// `a.b.0.c`, for tools that can't parse bracketed indices.
+    DottedIndex,
+}
+
+impl Default for PathStyle {
+    fn default() -> Self {
+        PathStyle::DottedBracket
+    }
+}
+
+impl PathStyle {
+    fn separator(self) -> char {
+        match self {
+            PathStyle::DottedBracket | PathStyle::DottedIndex => '.',
+            PathStyle::Slash => '/',
+        }
+    }
+}
+
 /// Performs depth-first traversal of the nested structure.
 #[derive(Clone)]
-struct FieldsIter<'a> {
+pub struct FieldsIter<'a> {
     /// Stack of iterators used for the depth-first traversal.
     stack: Vec<LeafIter<'a>>,
     /// Path components from the root up to the top of the stack.
     path: Vec<PathComponent<'a>>,
+    /// Whether maps and arrays are yielded before descending into them.
+    intermediates: bool,
+    /// Path of the traversed structure itself, prepended to every path.
+    prefix: String,
+    /// Whether keys are quoted as the lookup parser expects rather than escaped.
+    quote_keys: bool,
+    /// Maximum number of components in a yielded path.
+    max_depth: Option<usize>,
+    /// How path components are joined in the yielded paths.
+    path_style: PathStyle,
 }
 
 impl<'a> FieldsIter<'a> {
-    fn new(fields: &'a BTreeMap<String, Value>) -> FieldsIter<'a> {
+    pub fn new(fields: &'a BTreeMap<String, Value>) -> FieldsIter<'a> {
         FieldsIter {
             stack: vec![LeafIter::Map(fields.iter())],
             path: vec![],
+            intermediates: false,
+            prefix: String::new(),
+            quote_keys: false,
+            max_depth: None,
+            path_style: PathStyle::default(),
         }
     }
 
-    fn push(&mut self, value: &'a Value, component: PathComponent<'a>) -> Option<&'a Value> {
-        match value {
-            Value::Map(map) => {
-                self.stack.push(LeafIter::Map(map.iter()));
-                self.path.push(component);
-                None
-            }
-            Value::Array(array) => {
-                self.stack.push(LeafIter::Array(array.iter().enumerate()));
-                self.path.push(component);
-                None
-            }
-            _ => Some(value),
+    /// Traverse the map or array `value`, which is found at `prefix`.
+    pub(crate) fn nested(value: &'a Value, prefix: String) -> FieldsIter<'a> {
+        let stack = match value {
+            Value::Map(map) => vec![LeafIter::Map(map.iter())],
+            Value::Array(array) => vec![LeafIter::Array(array.iter().enumerate())],
+            _ => vec![],
+        };
+        FieldsIter {
+            stack,
+            path: vec![],
+            intermediates: false,
+            prefix,
+            quote_keys: false,
+            max_depth: None,
+            path_style: PathStyle::default(),
         }
     }
 
+    /// Also yield every map and array before descending into it, rather than only the leaves.
+    pub fn with_intermediates(mut self) -> Self {
+        self.intermediates = true;
+        self
+    }
+
+    /// Quote keys that contain periods, spaces or brackets, such as `a."b.c"`,
+    /// so the paths can be parsed back with `Lookup::from_str`.
+    ///
+    /// By default periods in keys are escaped instead, as in `a.b\.c`, which is the
+    /// form understood by `PathIter` and the `LogEvent` accessors.
+    pub fn with_quoted_keys(mut self) -> Self {
+        self.quote_keys = true;
+        self
+    }
+
+    /// Stop descending once paths are `depth` components long, yielding any map
+    /// or array found there as if it were a leaf. This bounds the work done on
+    /// deeply nested input.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Join path components using `style` rather than the default `a.b[0].c` form.
+    ///
+    /// Note that only the default style can be parsed back by `PathIter` or the
+    /// `LogEvent` accessors. The prefix of `fields_under` is kept as given.
+    pub fn with_path_style(mut self, style: PathStyle) -> Self {
+        self.path_style = style;
+        self
+    }
+
+    /// Descends into maps and arrays, returning the item to yield for `value`, if any.
+    fn visit(
+        &mut self,
+        value: &'a Value,
+        component: PathComponent<'a>,
+    ) -> Option<(String, &'a Value)> {
+        if matches!(self.max_depth, Some(depth) if self.stack.len() >= depth) {
+            return Some((self.make_path(component), value));
+        }
+
+        let iter = match value {
+            Value::Map(map) => LeafIter::Map(map.iter()),
+            Value::Array(array) => LeafIter::Array(array.iter().enumerate()),
+            _ => return Some((self.make_path(component), value)),
+        };
+
+        let item = if self.intermediates {
+            Some((self.make_path(component), value))
+        } else {
+            None
+        };
+        self.stack.push(iter);
+        self.path.push(component);
+        item
+    }
+
     fn pop(&mut self) {
         self.stack.pop();
         self.path.pop();
     }
 
     fn make_path(&mut self, component: PathComponent<'a>) -> String {
-        let mut res = String::new();
-        let mut path_iter = self.path.iter().chain(iter::once(&component)).peekable();
-        loop {
-            match path_iter.next() {
-                None => return res,
-                Some(PathComponent::Key(key)) => {
-                    if key.contains('.') {
-                        res.push_str(&key.replace(".", "\\."))
-                    } else {
-                        res.push_str(&key)
-                    }
+        make_path(
+            &self.prefix,
+            &self.path,
+            component,
+            self.quote_keys,
+            self.path_style,
+        )
+    }
+}
+
+/// Joins `prefix`, the components of `path` and the final `component` into a path string.
+pub(super) fn make_path(
+    prefix: &str,
+    path: &[PathComponent<'_>],
+    component: PathComponent<'_>,
+    quote_keys: bool,
+    style: PathStyle,
+) -> String {
+    let separator = style.separator();
+    let mut res = prefix.to_owned();
+    for (i, component) in path.iter().chain(iter::once(&component)).enumerate() {
+        let first = i == 0 && prefix.is_empty();
+        match component {
+            PathComponent::Key(key) => {
+                if !first {
+                    res.push(separator);
+                }
+                if quote_keys {
+                    res.push_str(&quote_field(key))
+                } else if key.contains(separator) {
+                    res.push_str(&key.replace(separator, &format!("\\{}", separator)))
+                } else {
+                    res.push_str(&key)
                 }
-                Some(PathComponent::Index(index)) => res.push_str(&format!("[{}]", index)),
-            }
-            if let Some(PathComponent::Key(_)) = path_iter.peek() {
-                res.push('.');
             }
+            PathComponent::Index(index) => match style {
+                PathStyle::DottedBracket => res.push_str(&format!("[{}]", index)),
+                PathStyle::Slash | PathStyle::DottedIndex => {
+                    if !first {
+                        res.push(separator);
+                    }
+                    res.push_str(&index.to_string())
+                }
+            },
         }
     }
+    res
 }
 
 impl<'a> Iterator for FieldsIter<'a> {
@@ -95,19 +260,16 @@ impl<'a> Iterator for FieldsIter<'a> {
                 Some(LeafIter::Map(map_iter)) => match map_iter.next() {
                     None => self.pop(),
                     Some((key, value)) => {
-                        if let Some(scalar_value) = self.push(value, PathComponent::Key(key)) {
-                            return Some((self.make_path(PathComponent::Key(key)), scalar_value));
+                        if let Some(item) = self.visit(value, PathComponent::Key(key)) {
+                            return Some(item);
                         }
                     }
                 },
                 Some(LeafIter::Array(array_iter)) => match array_iter.next() {
                     None => self.pop(),
                     Some((index, value)) => {
-                        if let Some(scalar_value) = self.push(value, PathComponent::Index(index)) {
-                            return Some((
-                                self.make_path(PathComponent::Index(index)),
-                                scalar_value,
-                            ));
+                        if let Some(item) = self.visit(value, PathComponent::Index(index)) {
+                            return Some(item);
                         }
                     }
                 },
@@ -181,4 +343,241 @@ mod test {
         let collected: Vec<_> = all_fields(&fields).collect();
         assert_eq!(collected, expected);
     }
+
+    #[test]
+    fn keys_nested_with_intermediates() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2]]
+            }
+        }));
+        let expected = vec![
+            "a",
+            "a.a",
+            "a.array",
+            "a.array[0]",
+            "a.array[1]",
+            "a.array[2]",
+            "a.array[2].x",
+            "a.array[3]",
+            "a.array[3][0]",
+            "a.b",
+            "a.b.c",
+        ];
+
+        let collected: Vec<_> = FieldsIter::new(&fields)
+            .with_intermediates()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(collected, expected);
+
+        let intermediates: BTreeMap<_, _> = FieldsIter::new(&fields).with_intermediates().collect();
+        assert!(matches!(intermediates["a"], Value::Map(_)));
+        assert!(matches!(intermediates["a.b"], Value::Map(_)));
+        assert!(matches!(intermediates["a.array"], Value::Array(_)));
+    }
+
+    #[test]
+    fn keys_under_prefix() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2]]
+            }
+        }));
+        let under = |prefix: &str| -> Vec<_> {
+            fields_under(&fields, &Lookup::from_str(prefix).unwrap()).collect()
+        };
+
+        let expected: Vec<_> = vec![
+            ("a.array[0]", &Value::Null),
+            ("a.array[1]", &Value::Integer(3)),
+            ("a.array[2].x", &Value::Integer(1)),
+            ("a.array[3][0]", &Value::Integer(2)),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.into(), v))
+        .collect();
+        assert_eq!(under("a.array"), expected);
+
+        let expected: Vec<_> = vec![("a.array[2].x".into(), &Value::Integer(1))];
+        assert_eq!(under("a.array[2]"), expected);
+
+        let expected: Vec<_> = vec![("a.a".into(), &Value::Integer(4))];
+        assert_eq!(under("a.a"), expected);
+
+        assert!(under("a.missing").is_empty());
+        assert!(under("a.a.deeper").is_empty());
+    }
+
+    #[test]
+    fn keys_quoted() {
+        let fields = fields_from_json(json!({
+            "weird.key": 1,
+            "a": {
+                "weird key": [2],
+                "plain": 3
+            }
+        }));
+        let expected: Vec<_> = vec![
+            (r#"a.plain"#, &Value::Integer(3)),
+            (r#"a."weird key"[0]"#, &Value::Integer(2)),
+            (r#""weird.key""#, &Value::Integer(1)),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.into(), v))
+        .collect();
+
+        let collected: Vec<_> = FieldsIter::new(&fields).with_quoted_keys().collect();
+        assert_eq!(collected, expected);
+
+        for (path, value) in collected {
+            let lookup = Lookup::from_str(&path).unwrap();
+            assert_eq!(lookup.to_string(), path);
+            let mut segments = lookup.iter().cloned().collect::<VecDeque<_>>();
+            let first = match segments.pop_front() {
+                Some(Segment::Field(field)) => field.name,
+                _ => panic!("paths start with a field"),
+            };
+            assert_eq!(
+                fields[first].get(Lookup::from(segments)).unwrap(),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn keys_match_lookup_sort_key() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "list": [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+                "weird key": {"x": 1}
+            },
+            "a-b": 2,
+            "a.b": 3,
+            "b": [{"y": 4}, {"x": 5}]
+        }));
+        let paths: Vec<_> = FieldsIter::new(&fields)
+            .with_intermediates()
+            .with_quoted_keys()
+            .map(|(path, _)| path)
+            .collect();
+
+        let mut lookups: Vec<_> = paths
+            .iter()
+            .rev()
+            .map(|path| Lookup::from_str(path).unwrap())
+            .collect();
+        lookups.sort_by_key(|lookup| lookup.to_alphabetical_sort_key());
+
+        let sorted: Vec<_> = lookups.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, paths);
+    }
+
+    #[test]
+    fn keys_depth_limited() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3]
+            },
+            "d": 6
+        }));
+
+        let collected: Vec<_> = FieldsIter::new(&fields).max_depth(1).collect();
+        assert_eq!(
+            collected,
+            vec![("a".into(), &fields["a"]), ("d".into(), &Value::Integer(6))]
+        );
+        assert!(matches!(collected[0].1, Value::Map(_)));
+
+        let collected: Vec<_> = FieldsIter::new(&fields)
+            .max_depth(2)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(collected, vec!["a.a", "a.array", "a.b", "d"]);
+    }
+
+    #[test]
+    fn keys_path_styles() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2]]
+            },
+            "a/b": 6
+        }));
+        let keys = |style| {
+            FieldsIter::new(&fields)
+                .with_path_style(style)
+                .map(|(k, _)| k)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            keys(PathStyle::Slash),
+            vec![
+                "a/a",
+                "a/array/0",
+                "a/array/1",
+                "a/array/2/x",
+                "a/array/3/0",
+                "a/b/c",
+                "a\\/b",
+            ]
+        );
+        assert_eq!(
+            keys(PathStyle::DottedIndex),
+            vec![
+                "a.a",
+                "a.array.0",
+                "a.array.1",
+                "a.array.2.x",
+                "a.array.3.0",
+                "a.b.c",
+                "a/b",
+            ]
+        );
+        assert_eq!(
+            keys(PathStyle::DottedBracket),
+            all_fields(&fields).map(|(k, _)| k).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn keys_under_prefix_slash_style() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "array": [null, {
+                    "x": 1
+                }]
+            }
+        }));
+        let value = &fields["a"];
+
+        let collected: Vec<_> = FieldsIter::nested(value, "a".into())
+            .with_path_style(PathStyle::Slash)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(collected, vec!["a/array/0", "a/array/1/x"]);
+    }
 }
@@ -0,0 +1,135 @@
+use super::all_fields::{make_path, PathComponent, PathStyle};
+use super::Value;
+use std::{
+    collections::{btree_map, BTreeMap},
+    iter, slice,
+};
+
+/// Iterates over all paths in form `a.b[0].c[1]` in alphabetical order
+/// and mutable references to their corresponding values.
+pub fn all_fields_mut(
+    fields: &mut BTreeMap<String, Value>,
+) -> impl Iterator<Item = (String, &mut Value)> {
+    FieldsIterMut::new(fields)
+}
+
+enum LeafIterMut<'a> {
+    Map(btree_map::IterMut<'a, String, Value>),
+    Array(iter::Enumerate<slice::IterMut<'a, Value>>),
+}
+
+/// Performs depth-first traversal of the nested structure, like `FieldsIter`,
+/// handing out mutable references to the leaves.
+///
+/// Each `&'a mut Value` is produced exactly once by one of the iterators on
+/// the stack. A map or array is never yielded; its reference is instead moved
+/// into the new iterator pushed onto the stack, so the only references that
+/// escape are to leaves and no two of them can alias.
+struct FieldsIterMut<'a> {
+    /// Stack of iterators used for the depth-first traversal.
+    stack: Vec<LeafIterMut<'a>>,
+    /// Path components from the root up to the top of the stack.
+    path: Vec<PathComponent<'a>>,
+}
+
+impl<'a> FieldsIterMut<'a> {
+    fn new(fields: &'a mut BTreeMap<String, Value>) -> FieldsIterMut<'a> {
+        FieldsIterMut {
+            stack: vec![LeafIterMut::Map(fields.iter_mut())],
+            path: vec![],
+        }
+    }
+
+    fn pop(&mut self) {
+        self.stack.pop();
+        self.path.pop();
+    }
+}
+
+impl<'a> Iterator for FieldsIterMut<'a> {
+    type Item = (String, &'a mut Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // The borrow of the stack ends here, the item itself lives for `'a`.
+            let next = match self.stack.last_mut() {
+                None => return None,
+                Some(LeafIterMut::Map(map_iter)) => map_iter
+                    .next()
+                    .map(|(key, value)| (PathComponent::Key(key), value)),
+                Some(LeafIterMut::Array(array_iter)) => array_iter
+                    .next()
+                    .map(|(index, value)| (PathComponent::Index(index), value)),
+            };
+
+            match next {
+                None => self.pop(),
+                Some((component, Value::Map(map))) => {
+                    self.stack.push(LeafIterMut::Map(map.iter_mut()));
+                    self.path.push(component);
+                }
+                Some((component, Value::Array(array))) => {
+                    self.stack
+                        .push(LeafIterMut::Array(array.iter_mut().enumerate()));
+                    self.path.push(component);
+                }
+                Some((component, value)) => {
+                    return Some((
+                        make_path("", &self.path, component, false, PathStyle::default()),
+                        value,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{all_fields, test::fields_from_json};
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn double_integers_nested() {
+        let mut fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2]]
+            },
+            "a.b.c": 6,
+        }));
+
+        let paths: Vec<_> = all_fields_mut(&mut fields)
+            .map(|(path, value)| {
+                if let Value::Integer(i) = value {
+                    *i *= 2;
+                }
+                path
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            all_fields(&fields).map(|(k, _)| k).collect::<Vec<_>>()
+        );
+
+        let expected = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 10
+                },
+                "a": 8,
+                "array": [null, 6, {
+                    "x": 2
+                }, [4]]
+            },
+            "a.b.c": 12,
+        }));
+        assert_eq!(fields, expected);
+    }
+}
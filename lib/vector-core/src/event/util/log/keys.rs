@@ -58,4 +58,23 @@ mod test {
         let collected: Vec<_> = keys(&fields).collect();
         assert_eq!(collected, expected);
     }
+
+    #[test]
+    fn keys_match_all_fields() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2]]
+            }
+        }));
+
+        let expected: Vec<_> = all_fields(&fields).map(|(k, _)| k).collect();
+        let collected: Vec<_> = keys(&fields).collect();
+        assert_eq!(collected, expected);
+    }
 }
@@ -1,21 +1,25 @@
 mod all_fields;
+mod all_fields_mut;
 mod contains;
 mod get;
 mod get_mut;
 mod insert;
 mod keys;
+mod pairs;
 mod path_iter;
 mod remove;
 
 pub(self) use super::Value;
 
-pub use all_fields::all_fields;
+pub use all_fields::{all_fields, fields_under, FieldsIter, PathStyle};
+pub use all_fields_mut::all_fields_mut;
 pub use contains::contains;
 pub use get::get;
 pub use get::get_value;
 pub use get_mut::get_mut;
 pub use insert::{insert, insert_path};
 pub use keys::keys;
+pub use pairs::pairs;
 pub use path_iter::{PathComponent, PathIter};
 pub use remove::remove;
 
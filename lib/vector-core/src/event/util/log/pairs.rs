@@ -0,0 +1,75 @@
+use super::Value;
+use lookup::Lookup;
+use std::collections::BTreeMap;
+
+/// Iterates over all leaf paths in alphabetical order and their corresponding values.
+///
+/// This performs the same depth-first traversal as `all_fields`, but yields structured `Lookup`s
+/// rather than pre-stringified paths. As with `all_fields`, empty maps and arrays aren't yielded.
+pub fn pairs(fields: &BTreeMap<String, Value>) -> impl Iterator<Item = (Lookup<'_>, &Value)> {
+    fields
+        .iter()
+        .flat_map(|(key, value)| value.pairs(Some(Lookup::from(key)), true))
+        .filter(|(_, value)| !matches!(value, Value::Map(_) | Value::Array(_)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::fields_from_json;
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn keys_simple() {
+        let fields = fields_from_json(json!({
+            "field2": 3,
+            "field1": 4,
+            "field3": 5
+        }));
+        let expected: Vec<_> = vec![
+            ("field1", &Value::Integer(4)),
+            ("field2", &Value::Integer(3)),
+            ("field3", &Value::Integer(5)),
+        ]
+        .into_iter()
+        .map(|(k, v)| (Lookup::from_str(k).unwrap(), v))
+        .collect();
+
+        let collected: Vec<_> = pairs(&fields).collect();
+        assert_eq!(collected, expected);
+    }
+
+    #[test]
+    fn keys_nested() {
+        let fields = fields_from_json(json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2], []]
+            },
+            "a.b.c": 6,
+        }));
+        let expected: Vec<_> = vec![
+            (Lookup::from_str("a.a").unwrap(), &Value::Integer(4)),
+            (Lookup::from_str("a.array[0]").unwrap(), &Value::Null),
+            (Lookup::from_str("a.array[1]").unwrap(), &Value::Integer(3)),
+            (
+                Lookup::from_str("a.array[2].x").unwrap(),
+                &Value::Integer(1),
+            ),
+            (
+                Lookup::from_str("a.array[3][0]").unwrap(),
+                &Value::Integer(2),
+            ),
+            (Lookup::from_str("a.b.c").unwrap(), &Value::Integer(5)),
+            (Lookup::from("a.b.c"), &Value::Integer(6)),
+        ];
+
+        let collected: Vec<_> = pairs(&fields).collect();
+        assert_eq!(collected, expected);
+    }
+}
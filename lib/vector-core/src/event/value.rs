@@ -1,4 +1,9 @@
-use crate::{event::error::EventError, event::timestamp_to_string, Result};
+use crate::{
+    event::error::EventError,
+    event::timestamp_to_string,
+    event::util::log::{FieldsIter, PathStyle},
+    Result,
+};
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use lookup::{Field, FieldBuf, Lookup, LookupBuf, Segment, SegmentBuf};
@@ -272,6 +277,15 @@ impl Value {
         self.as_bytes()
     }
 
+    /// Returns the bytes of a `Value::Bytes`, or `None` for any other kind.
+    /// Unlike `as_bytes`, nothing is stringified.
+    pub fn as_bytes_opt(&self) -> Option<&Bytes> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
     pub fn as_map(&self) -> Option<&BTreeMap<String, Value>> {
         match &self {
             Value::Map(map) => Some(map),
@@ -293,6 +307,27 @@ impl Value {
         }
     }
 
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
     /// Returns self as a mutable `BTreeMap<String, Value>`
     ///
     /// # Panics
@@ -317,6 +352,14 @@ impl Value {
         }
     }
 
+    /// Returns the elements of a `Value::Array`, or `None` for any other kind.
+    pub fn as_array_opt(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
     /// Returns self as a mutable `Vec<Value>`
     ///
     /// # Panics
@@ -329,7 +372,8 @@ impl Value {
         }
     }
 
-    pub fn kind(&self) -> &str {
+    /// Returns the name of the value's kind, for use in diagnostics.
+    pub fn kind(&self) -> &'static str {
         match self {
             Value::Bytes(_) => "string",
             Value::Timestamp(_) => "timestamp",
@@ -357,6 +401,106 @@ impl Value {
         }
     }
 
+    /// Deeply merges `incoming` value into self.
+    ///
+    /// Maps are merged key by key, recursing into keys present in both.
+    /// Arrays are concatenated, with `incoming`'s elements after self's. In
+    /// every other case, including when the two kinds differ, `incoming`
+    /// replaces self.
+    pub fn deep_merge(&mut self, incoming: Value) {
+        match (self, incoming) {
+            (Value::Map(current), Value::Map(incoming)) => {
+                for (key, incoming) in incoming {
+                    match current.get_mut(&key) {
+                        Some(current) => current.deep_merge(incoming),
+                        None => {
+                            current.insert(key, incoming);
+                        }
+                    }
+                }
+            }
+            (Value::Array(current), Value::Array(incoming)) => current.extend(incoming),
+            (current, incoming) => *current = incoming,
+        }
+    }
+
+    /// Returns an approximate count of the bytes this value occupies, including
+    /// the strings, keys and nested values it owns.
+    ///
+    /// This is meant for consistent accounting rather than an exact measure:
+    /// allocator overhead and spare capacity are ignored.
+    pub fn byte_size_estimate(&self) -> usize {
+        let owned = match self {
+            Value::Bytes(bytes) => bytes.len(),
+            Value::Map(map) => map
+                .iter()
+                .map(|(key, value)| key.len() + value.byte_size_estimate())
+                .sum(),
+            Value::Array(array) => array.iter().map(Value::byte_size_estimate).sum(),
+            Value::Integer(_)
+            | Value::Float(_)
+            | Value::Boolean(_)
+            | Value::Timestamp(_)
+            | Value::Null => 0,
+        };
+        std::mem::size_of::<Value>() + owned
+    }
+
+    /// Truncates every string in this value, including those nested in maps and arrays, to at most
+    /// `max_bytes` bytes. Map keys are left as they are.
+    ///
+    /// A string is cut at the start of the UTF-8 character that would cross the limit, so it may
+    /// end up a few bytes shorter than `max_bytes` but is never left with a partial character.
+    ///
+    /// ```rust
+    /// use vector_core::event::Value;
+    ///
+    /// let mut value = Value::from("héllo");
+    /// value.truncate_strings(2);
+    /// assert_eq!(value, Value::from("h"));
+    /// ```
+    pub fn truncate_strings(&mut self, max_bytes: usize) {
+        match self {
+            Value::Bytes(bytes) if bytes.len() > max_bytes => {
+                let mut end = max_bytes;
+                // Back off over continuation bytes, which look like `0b10xx_xxxx`.
+                while end > 0 && bytes[end] & 0xC0 == 0x80 {
+                    end -= 1;
+                }
+                bytes.truncate(end);
+            }
+            Value::Map(map) => map
+                .values_mut()
+                .for_each(|value| value.truncate_strings(max_bytes)),
+            Value::Array(array) => array
+                .iter_mut()
+                .for_each(|value| value.truncate_strings(max_bytes)),
+            _ => {}
+        }
+    }
+
+    /// Flattens nested maps and arrays into a single-level map from the path of each leaf, joined
+    /// according to `style`, to a copy of the leaf.
+    ///
+    /// Array elements are keyed by their index: `list[0]` with `PathStyle::DottedBracket`,
+    /// `list/0` with `PathStyle::Slash` and `list.0` with `PathStyle::DottedIndex`. Empty maps and
+    /// arrays have no leaves and are left out, and a value that is neither a map nor an array
+    /// flattens to an empty map.
+    ///
+    /// ```rust
+    /// use vector_core::event::{util::log::PathStyle, Value};
+    ///
+    /// let value = Value::from(serde_json::json!({ "a": { "b": [1, 2] } }));
+    /// let flat = value.flatten(PathStyle::DottedBracket);
+    /// assert_eq!(flat["a.b[1]"], Value::from(2));
+    /// ```
+    pub fn flatten(&self, style: PathStyle) -> BTreeMap<String, Value> {
+        FieldsIter::nested(self, String::new())
+            .with_path_style(style)
+            .map(|(path, value)| (path, value.clone()))
+            .collect()
+    }
+
     /// Return if the node is empty, that is, it is an array or map with no items.
     ///
     /// ```rust
@@ -457,6 +601,9 @@ impl Value {
                     SegmentBuf::Index(next_len) => {
                         Value::Array(Vec::with_capacity(next_len.abs() as usize))
                     }
+                    SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => {
+                        Value::Array(Vec::new())
+                    }
                     SegmentBuf::Field(_) | SegmentBuf::Coalesce(_) => {
                         Value::Map(Default::default())
                     }
@@ -598,6 +745,9 @@ impl Value {
                         inner
                     }
                 },
+                Some(SegmentBuf::IndexWildcard) | Some(SegmentBuf::Range { .. }) => {
+                    return Err(EventError::AmbiguousInsert)
+                }
                 None => value,
             };
             array.push(next_val);
@@ -611,6 +761,12 @@ impl Value {
 
     /// Insert a value at a given lookup.
     ///
+    /// Maps and arrays missing along the lookup are created, and values of the wrong type are
+    /// replaced by them. When an index is beyond the end of an array, the array is padded with
+    /// `Value::Null` up to the index, so inserting at `foo[2]` into an empty map results in
+    /// `{ "foo": [null, null, value] }`. A negative index beyond the start of an array pads it at
+    /// the front instead.
+    ///
     /// ```rust
     /// use vector_core::event::Value;
     /// use lookup::Lookup;
@@ -625,6 +781,12 @@ impl Value {
     ///
     /// assert!(map.contains("bar"));
     /// assert!(map.contains(Lookup::from_str("star.baz").unwrap()));
+    ///
+    /// map.insert(Lookup::from_str("foo[2]").unwrap(), true);
+    /// assert_eq!(
+    ///     map.get("foo").unwrap(),
+    ///     Some(&Value::from(vec![Value::Null, Value::Null, Value::from(true)]))
+    /// );
     /// ```
     #[allow(clippy::missing_errors_doc)]
     pub fn insert(
@@ -637,6 +799,16 @@ impl Value {
         let span = trace_span!("insert", lookup = %working_lookup);
         let _guard = span.enter();
 
+        // Wildcards and ranges don't address a single location, so check for them before
+        // anything is created along the way.
+        if working_lookup
+            .iter()
+            .any(|segment| segment.is_index_wildcard() || segment.is_range())
+        {
+            trace!("Cannot insert into a wildcard or range index.");
+            return Err(EventError::AmbiguousInsert);
+        }
+
         let this_segment = working_lookup.pop_front();
         match (this_segment, self) {
             // We've met an end and found our value.
@@ -645,6 +817,9 @@ impl Value {
                 core::mem::swap(&mut value, item);
                 Ok(Some(value))
             }
+            (Some(SegmentBuf::IndexWildcard), _) | (Some(SegmentBuf::Range { .. }), _) => {
+                Err(EventError::AmbiguousInsert)
+            }
             // This is just not allowed and should not occur.
             // The top level insert will always be a map (or an array in tests).
             // Then for further descents into the lookup, in the `insert_map` function
@@ -697,6 +872,9 @@ impl Value {
     ///
     /// Setting `prune` to true will also remove the entries of maps and arrays that are emptied.
     ///
+    /// Removing an array element shifts the elements after it down by one, rather than leaving a
+    /// null in its place.
+    ///
     /// ```rust
     /// use vector_core::event::Value;
     /// use lookup::Lookup;
@@ -800,6 +978,10 @@ impl Value {
             }
             (Some(Segment::Index(_)), Value::Map(_))
             | (Some(Segment::Field { .. }), Value::Array(_)) => Ok(None),
+            (Some(Segment::IndexWildcard), _) | (Some(Segment::Range { .. }), _) => {
+                trace!("Wildcard and range indices can't be removed.");
+                Ok(None)
+            }
             // Descend into an array
             (Some(Segment::Index(i)), Value::Array(array)) => {
                 let index = if i.is_negative() {
@@ -813,8 +995,7 @@ impl Value {
                 };
 
                 if working_lookup.is_empty() {
-                    // We don't **actually** want to remove the index, we just
-                    // want to swap it with a null.
+                    // Later elements shift down to fill the gap.
                     if array.len() > index {
                         Ok(Some(array.remove(index)))
                     } else {
@@ -901,6 +1082,10 @@ impl Value {
                 }
             }
             (Some(Segment::Index(_)), Value::Map(_)) => Ok(None),
+            (Some(Segment::IndexWildcard), _) | (Some(Segment::Range { .. }), _) => {
+                trace!("Wildcard and range indices resolve to many values, use `get_all`.");
+                Ok(None)
+            }
             // Descend into an array
             (Some(Segment::Index(i)), Value::Array(array)) => {
                 let index = if i.is_negative() {
@@ -935,6 +1120,59 @@ impl Value {
         }
     }
 
+    /// Get immutable borrows of every value matched by the lookup.
+    ///
+    /// Unlike `get`, wildcard indices (`[*]`) are expanded to every index present in the array,
+    /// and ranges (`[0..2]`) to every index present within their bounds.
+    ///
+    /// ```rust
+    /// use vector_core::event::Value;
+    /// use lookup::Lookup;
+    ///
+    /// let mut value = Value::from(std::collections::BTreeMap::default());
+    /// value.insert(lookup::LookupBuf::from_str("foo[0].bar").unwrap(), 1).unwrap();
+    /// value.insert(lookup::LookupBuf::from_str("foo[1].bar").unwrap(), 2).unwrap();
+    ///
+    /// let lookup_key = Lookup::from_str("foo[*].bar").unwrap();
+    /// assert_eq!(value.get_all(lookup_key), vec![&Value::from(1), &Value::from(2)]);
+    /// ```
+    pub fn get_all<'a>(&self, lookup: impl Into<Lookup<'a>> + Debug) -> Vec<&Value> {
+        let lookup = lookup.into();
+        if !lookup
+            .iter()
+            .any(|segment| segment.is_index_wildcard() || segment.is_range())
+        {
+            return self.get(lookup).ok().flatten().into_iter().collect();
+        }
+
+        let mut working_lookup = lookup;
+        match working_lookup.pop_front() {
+            None => vec![self],
+            Some(Segment::IndexWildcard) => match self {
+                Value::Array(array) => array
+                    .iter()
+                    .flat_map(|inner| inner.get_all(working_lookup.clone()))
+                    .collect(),
+                _ => Vec::new(),
+            },
+            Some(Segment::Range { start, end }) => match self {
+                Value::Array(array) => {
+                    let end = end.unwrap_or_else(|| array.len()).min(array.len());
+                    let start = start.unwrap_or(0).min(end);
+                    array[start..end]
+                        .iter()
+                        .flat_map(|inner| inner.get_all(working_lookup.clone()))
+                        .collect()
+                }
+                _ => Vec::new(),
+            },
+            Some(segment) => match self.get(Lookup::from(segment)) {
+                Ok(Some(inner)) => inner.get_all(working_lookup),
+                _ => Vec::new(),
+            },
+        }
+    }
+
     /// Get a mutable borrow of the value by lookup.
     ///
     /// ```rust
@@ -1005,7 +1243,9 @@ impl Value {
                 }
             }
             (Some(Segment::Index(_)), Value::Map(_))
-            | (Some(Segment::Field(_)), Value::Array(_)) => Ok(None),
+            | (Some(Segment::Field(_)), Value::Array(_))
+            | (Some(Segment::IndexWildcard), _)
+            | (Some(Segment::Range { .. }), _) => Ok(None),
             // Descend into an array
             (Some(Segment::Index(i)), Value::Array(array)) => {
                 let index = if i.is_negative() {
@@ -1263,9 +1503,37 @@ mod test {
         Ok(buf)
     }
 
+    #[test]
+    fn kind() {
+        let cases = vec![
+            (Value::from("foo"), "string"),
+            (Value::from(1), "integer"),
+            (Value::from(1.5), "float"),
+            (Value::from(true), "boolean"),
+            (Value::from(Utc::now()), "timestamp"),
+            (Value::from(BTreeMap::new()), "map"),
+            (Value::from(Vec::<Value>::new()), "array"),
+            (Value::Null, "null"),
+        ];
+
+        for (value, kind) in cases {
+            assert_eq!(value.kind(), kind);
+        }
+    }
+
     mod insert_get_remove {
         use super::*;
 
+        #[test]
+        fn root() {
+            let mut value = Value::from(BTreeMap::default());
+            value.insert("root", true).unwrap();
+            let mut expected = value.clone();
+            assert_eq!(value.get(Lookup::root()).unwrap(), Some(&expected));
+            assert_eq!(value.get_mut(Lookup::root()).unwrap(), Some(&mut expected));
+            assert!(value.contains(Lookup::root()));
+        }
+
         #[test]
         fn single_field() {
             let mut value = Value::from(BTreeMap::default());
@@ -1326,6 +1594,35 @@ mod test {
             assert_eq!(value.remove(&lookup, false).unwrap(), Some(marker));
         }
 
+        #[test]
+        fn creates_intermediates_and_pads_arrays() {
+            let mut value = Value::from(BTreeMap::default());
+            let lookup = LookupBuf::from_str("a.b[2]").unwrap();
+            let marker = Value::from(true);
+            assert_eq!(value.insert(lookup.clone(), marker.clone()).unwrap(), None);
+
+            let mut b = BTreeMap::new();
+            b.insert(
+                "b".to_string(),
+                Value::from(vec![Value::Null, Value::Null, marker.clone()]),
+            );
+            let mut expected = BTreeMap::new();
+            expected.insert("a".to_string(), Value::from(b));
+            assert_eq!(value, Value::from(expected));
+            assert_eq!(value.get(&lookup).unwrap(), Some(&marker));
+
+            // Inserting below a padding null turns it into a map without resizing.
+            let lookup = LookupBuf::from_str("a.b[1].c").unwrap();
+            assert_eq!(value.insert(lookup.clone(), 1).unwrap(), None);
+            assert_eq!(value.get(&lookup).unwrap(), Some(&Value::from(1)));
+            assert_eq!(
+                value.as_map().unwrap()["a"].as_map().unwrap()["b"]
+                    .as_array()
+                    .len(),
+                3
+            );
+        }
+
         #[test]
         fn negative_index() {
             let mut value = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
@@ -1350,6 +1647,19 @@ mod test {
             assert_eq!(value.as_array()[1], Value::from(3));
         }
 
+        #[test]
+        fn negative_index_get() {
+            let mut value = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
+            let lookup = LookupBuf::from_str("[-1]").unwrap();
+            assert_eq!(value.get(&lookup).unwrap(), Some(&Value::from(3)));
+            assert_eq!(value.get_mut(&lookup).unwrap(), Some(&mut Value::from(3)));
+
+            // Indices before the start of the array don't resolve.
+            let lookup = LookupBuf::from_str("[-4]").unwrap();
+            assert_eq!(value.get(&lookup).unwrap(), None);
+            assert_eq!(value.get_mut(&lookup).unwrap(), None);
+        }
+
         #[test]
         fn negative_index_resize() {
             let mut value = Value::from(Vec::<Value>::default());
@@ -1365,6 +1675,57 @@ mod test {
             assert_eq!(value.get(&lookup).unwrap(), Some(&marker));
         }
 
+        #[test]
+        fn wildcard_index() {
+            let mut value = Value::from(BTreeMap::default());
+            value
+                .insert(LookupBuf::from_str("root[0].doot").unwrap(), 1)
+                .unwrap();
+            value
+                .insert(LookupBuf::from_str("root[1].doot").unwrap(), 2)
+                .unwrap();
+
+            let lookup = LookupBuf::from_str("root[*].doot").unwrap();
+            assert_eq!(
+                value.get_all(&lookup),
+                vec![&Value::from(1), &Value::from(2)]
+            );
+            assert_eq!(value.get(&lookup).unwrap(), None);
+            assert!(matches!(
+                value.insert(lookup, true),
+                Err(EventError::AmbiguousInsert)
+            ));
+            assert_eq!(value.as_map().unwrap()["root"].as_array().len(), 2);
+        }
+
+        #[test]
+        fn range_index() {
+            let mut value = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
+            let get_all = |value: &Value, key: &'static str| {
+                value
+                    .get_all(&LookupBuf::from_str(key).unwrap())
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(
+                get_all(&value, "[0..2]"),
+                vec![Value::from(1), Value::from(2)]
+            );
+            assert_eq!(get_all(&value, "[..1]"), vec![Value::from(1)]);
+            assert_eq!(
+                get_all(&value, "[1..]"),
+                vec![Value::from(2), Value::from(3)]
+            );
+            assert_eq!(get_all(&value, "[2..10]"), vec![Value::from(3)]);
+            assert!(get_all(&value, "[5..]").is_empty());
+            assert!(matches!(
+                value.insert(LookupBuf::from_str("[0..2]").unwrap(), true),
+                Err(EventError::AmbiguousInsert)
+            ));
+        }
+
         #[test]
         fn nested_index() {
             let mut value = Value::from(Vec::<Value>::default());
@@ -1497,9 +1858,80 @@ mod test {
         }
     }
 
+    mod sufficiently_complex {
+        use super::*;
+
+        const SUFFICIENTLY_COMPLEX: &str = r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[0].00numericstart.nested_lookup[0][0]"#;
+
+        #[test]
+        fn get_and_get_mut() {
+            let mut value = Value::from(BTreeMap::default());
+            let lookup = LookupBuf::from_str(SUFFICIENTLY_COMPLEX).unwrap();
+            let mut marker = Value::from(true);
+            assert_eq!(value.insert(lookup.clone(), marker.clone()).unwrap(), None);
+
+            assert_eq!(value.get(&lookup).unwrap(), Some(&marker));
+            assert_eq!(value.get_mut(&lookup).unwrap(), Some(&mut marker));
+
+            let intermediate = LookupBuf::from_str(
+                r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[0]"#,
+            )
+            .unwrap();
+            assert!(matches!(
+                value.get(&intermediate).unwrap(),
+                Some(Value::Map(_))
+            ));
+        }
+
+        #[test]
+        fn missing_segments() {
+            let mut value = Value::from(BTreeMap::default());
+            let lookup = LookupBuf::from_str(SUFFICIENTLY_COMPLEX).unwrap();
+            value.insert(lookup, true).unwrap();
+
+            for missing in &[
+                // Out of range index.
+                r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[1].00numericstart"#,
+                r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[0].00numericstart.nested_lookup[0][3]"#,
+                // Missing key.
+                r#"regular."quoted"."quoted but spaces"."quoted.but.periods".lookup[0].missing"#,
+                r#"regular.missing"#,
+            ] {
+                let lookup = LookupBuf::from_str(missing).unwrap();
+                assert_eq!(value.get(&lookup).unwrap(), None, "{}", missing);
+                assert_eq!(value.get_mut(&lookup).unwrap(), None, "{}", missing);
+            }
+        }
+    }
+
     mod corner_cases {
         use super::*;
 
+        #[test]
+        fn remove_nested_field_keeps_siblings() {
+            let mut value = Value::from(BTreeMap::default());
+            value.insert("foo.bar", 1).unwrap();
+            value.insert("foo.baz", 2).unwrap();
+            assert_eq!(value.remove("foo.bar", true).unwrap(), Some(Value::from(1)));
+            assert!(!value.contains("foo.bar"));
+            assert_eq!(value.get("foo.baz").unwrap(), Some(&Value::from(2)));
+            assert_eq!(value.remove("foo.bar", true).unwrap(), None);
+        }
+
+        #[test]
+        fn remove_array_element_shifts() {
+            let mut value = Value::from(BTreeMap::default());
+            value
+                .insert("foo", vec![Value::from(1), Value::from(2), Value::from(3)])
+                .unwrap();
+            let lookup = LookupBuf::from_str("foo[1]").unwrap();
+            assert_eq!(value.remove(&lookup, false).unwrap(), Some(Value::from(2)));
+            assert_eq!(
+                value.get("foo").unwrap(),
+                Some(&Value::from(vec![Value::from(1), Value::from(3)]))
+            );
+        }
+
         #[test]
         fn remove_prune_map_with_map() {
             let mut value = Value::from(BTreeMap::default());
@@ -1641,4 +2073,207 @@ mod test {
                 _ => panic!("This test should never read Err'ing type folders."),
             })
     }
+
+    #[test]
+    fn typed_accessors() {
+        let timestamp = Utc::now();
+        let integer = Value::from(1);
+        let float = Value::from(1.5);
+        let boolean = Value::from(true);
+        let timestamp_value = Value::from(timestamp);
+        let map = Value::from(BTreeMap::new());
+        let bytes = Value::from("foo");
+        let array = Value::from(vec![Value::from(1)]);
+
+        assert_eq!(integer.as_integer(), Some(1));
+        assert_eq!(float.as_integer(), None);
+
+        assert_eq!(float.as_float(), Some(1.5));
+        assert_eq!(integer.as_float(), None);
+
+        assert_eq!(boolean.as_bool(), Some(true));
+        assert_eq!(integer.as_bool(), None);
+
+        assert_eq!(timestamp_value.as_timestamp(), Some(&timestamp));
+        assert_eq!(boolean.as_timestamp(), None);
+
+        assert_eq!(map.as_map(), Some(&BTreeMap::new()));
+        assert_eq!(boolean.as_map(), None);
+
+        assert_eq!(bytes.as_bytes_opt(), Some(&Bytes::from("foo")));
+        assert_eq!(integer.as_bytes_opt(), None);
+        assert_eq!(map.as_bytes_opt(), None);
+
+        assert_eq!(array.as_array_opt(), Some(&vec![Value::from(1)]));
+        assert_eq!(map.as_array_opt(), None);
+        assert_eq!(bytes.as_array_opt(), None);
+    }
+
+    #[test]
+    fn deep_merge() {
+        let mut value = Value::from(serde_json::json!({
+            "a": { "b": 1, "c": { "d": "x" } },
+            "list": [1, 2],
+            "replaced": { "nested": true },
+            "kept": "yes",
+        }));
+        value.deep_merge(Value::from(serde_json::json!({
+            "a": { "b": 2, "c": { "e": "y" } },
+            "list": [3],
+            "replaced": "scalar",
+            "added": null,
+        })));
+
+        assert_eq!(
+            value,
+            Value::from(serde_json::json!({
+                "a": { "b": 2, "c": { "d": "x", "e": "y" } },
+                "list": [1, 2, 3],
+                "replaced": "scalar",
+                "kept": "yes",
+                "added": null,
+            }))
+        );
+    }
+
+    #[test]
+    fn truncate_strings() {
+        let mut value = Value::from(serde_json::json!({
+            "short": "abc",
+            "number": 123_456,
+            "a": {
+                "b": [{
+                    "deep": "abcdefgh"
+                }],
+                "accents": "ééé"
+            },
+        }));
+        value.truncate_strings(5);
+
+        assert_eq!(
+            value,
+            Value::from(serde_json::json!({
+                "short": "abc",
+                "number": 123_456,
+                "a": {
+                    "b": [{
+                        "deep": "abcde"
+                    }],
+                    // Each `é` is two bytes long, so the third one can't fit.
+                    "accents": "éé"
+                },
+            }))
+        );
+        let accents = Lookup::from_str("a.accents").unwrap();
+        let accents = value.get(accents).unwrap().unwrap().as_bytes();
+        assert_eq!(std::str::from_utf8(&accents).unwrap(), "éé");
+
+        let mut value = Value::from("🦀");
+        value.truncate_strings(3);
+        assert_eq!(value, Value::from(""));
+    }
+
+    #[test]
+    fn flatten() {
+        let value = Value::from(serde_json::json!({
+            "a": {
+                "b": {
+                    "c": 5
+                },
+                "a": 4,
+                "array": [null, 3, {
+                    "x": 1
+                }, [2]]
+            },
+            "empty": {},
+        }));
+
+        let expected = |keys: [&str; 6]| {
+            keys.iter()
+                .map(|key| key.to_string())
+                .zip(vec![
+                    Value::from(4),
+                    Value::Null,
+                    Value::from(3),
+                    Value::from(1),
+                    Value::from(2),
+                    Value::from(5),
+                ])
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        assert_eq!(
+            value.flatten(PathStyle::DottedBracket),
+            expected([
+                "a.a",
+                "a.array[0]",
+                "a.array[1]",
+                "a.array[2].x",
+                "a.array[3][0]",
+                "a.b.c",
+            ])
+        );
+        assert_eq!(
+            value.flatten(PathStyle::Slash),
+            expected([
+                "a/a",
+                "a/array/0",
+                "a/array/1",
+                "a/array/2/x",
+                "a/array/3/0",
+                "a/b/c",
+            ])
+        );
+        assert_eq!(
+            value.flatten(PathStyle::DottedIndex),
+            expected([
+                "a.a",
+                "a.array.0",
+                "a.array.1",
+                "a.array.2.x",
+                "a.array.3.0",
+                "a.b.c",
+            ])
+        );
+
+        let array = Value::from(serde_json::json!([{ "x": 1 }, 2]));
+        assert_eq!(
+            array.flatten(PathStyle::DottedBracket),
+            vec![
+                ("[0].x".to_string(), Value::from(1)),
+                ("[1]".to_string(), Value::from(2)),
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert!(Value::from("scalar")
+            .flatten(PathStyle::DottedBracket)
+            .is_empty());
+    }
+
+    #[test]
+    fn byte_size_estimate() {
+        let leaves = vec![
+            ("nested.message", Value::from("hello world")),
+            ("nested.count", Value::from(1)),
+            ("nested.list[0]", Value::from(true)),
+            ("timestamp", Value::from(Utc::now())),
+        ];
+        let leaves_estimate: usize = leaves
+            .iter()
+            .map(|(_, leaf)| leaf.byte_size_estimate())
+            .sum();
+
+        let mut value = Value::from(BTreeMap::default());
+        for (path, leaf) in leaves {
+            value
+                .insert(LookupBuf::from_str(path).unwrap(), leaf)
+                .unwrap();
+        }
+
+        assert!(value.byte_size_estimate() > leaves_estimate);
+        assert!(
+            Value::from("hello world").byte_size_estimate() > Value::from("").byte_size_estimate()
+        );
+    }
 }
@@ -191,6 +191,16 @@ impl KindInfo {
                     let mut set = BTreeSet::new();
                     set.insert(TypeKind::Array(map));
 
+                    self = KindInfo::Known(set);
+                }
+                SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => {
+                    // Wildcards and ranges address many elements of the array.
+                    let mut map = BTreeMap::default();
+                    map.insert(Index::Any, self);
+
+                    let mut set = BTreeSet::new();
+                    set.insert(TypeKind::Array(map));
+
                     self = KindInfo::Known(set);
                 }
             }
@@ -266,6 +276,14 @@ impl KindInfo {
                                 }
                             }
                         },
+                        SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => {
+                            match kind.array() {
+                                None => KindInfo::Unknown,
+                                Some(kind) => {
+                                    kind.get(&Index::Any).cloned().unwrap_or(KindInfo::Unknown)
+                                }
+                            }
+                        }
                     },
                 };
 
@@ -31,6 +31,9 @@ impl Value {
                     array.push(self);
                     self = Value::Array(array);
                 }
+                SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => {
+                    self = Value::Array(vec![self]);
+                }
             }
         }
 
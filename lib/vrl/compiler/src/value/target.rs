@@ -182,6 +182,7 @@ impl Value {
                     .checked_rem_euclid(len)
                     .and_then(|i| array.get(i as usize))
             }),
+            SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => None,
         }
     }
 
@@ -219,6 +220,7 @@ impl Value {
                     .checked_rem_euclid(len)
                     .and_then(move |i| array.get_mut(i as usize))
             }),
+            SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => None,
         }
     }
 
@@ -279,6 +281,7 @@ impl Value {
                     .checked_rem_euclid(len)
                     .map(|i| array.remove(i as usize))
             }),
+            SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => None,
         };
     }
 
@@ -426,6 +429,9 @@ impl Value {
                         .insert_by_segments(segments, new);
                 }
             }
+            // Wildcards and ranges don't address a single element, so there
+            // is nothing to update.
+            SegmentBuf::IndexWildcard | SegmentBuf::Range { .. } => {}
         }
     }
 }
@@ -14,7 +14,7 @@ impl Function for ParseTokens {
             title: "valid",
             source: r#"parse_tokens(s'A sentence "with \"a\" sentence inside" and [some brackets]')"#,
             result: Ok(
-                r#"["A", "sentence", "with \\\"a\\\" sentence inside", "and", "some brackets"]"#,
+                r#"["A", "sentence", "with \"a\" sentence inside", "and", "some brackets"]"#,
             ),
         }]
     }
@@ -46,9 +46,9 @@ impl Expression for ParseTokensFn {
 
         let tokens: Value = tokenize::parse(&string)
             .into_iter()
-            .map(|token| match token {
+            .map(|token| match token.as_ref() {
                 "" | "-" => Value::Null,
-                _ => token.to_owned().into(),
+                _ => token.into_owned().into(),
             })
             .collect::<Vec<_>>()
             .into();
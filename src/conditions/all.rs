@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{AnyCondition, Condition, ConditionConfig, ConditionDescription},
+    config::GenerateConfig,
+    event::Event,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AllConfig {
+    pub conditions: Vec<AnyCondition>,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<AllConfig>("all")
+}
+
+impl GenerateConfig for AllConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"conditions = [
+                { type = "is_log" },
+                { type = "check_fields", "message.eq" = "hooray" },
+            ]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[typetag::serde(name = "all")]
+impl ConditionConfig for AllConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        let conditions = self
+            .conditions
+            .iter()
+            .map(AnyCondition::build)
+            .collect::<crate::Result<_>>()?;
+        Ok(Box::new(All { conditions }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Passes when every inner condition passes, stopping at the first that
+/// doesn't.
+#[derive(Clone)]
+pub struct All {
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl Condition for All {
+    fn check(&self, e: &Event) -> bool {
+        self.conditions.iter().all(|condition| condition.check(e))
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        self.conditions
+            .iter()
+            .try_for_each(|condition| condition.check_with_context(e))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        conditions::{is_log::IsLogConfig, is_metric::IsMetricConfig},
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AllConfig>();
+    }
+
+    #[test]
+    fn all_basic() {
+        let metric = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let log = Event::from("just a log");
+
+        let cond = AllConfig {
+            conditions: vec![
+                AnyCondition::Map(Box::new(IsLogConfig {})),
+                AnyCondition::String(r#".message == "just a log""#.into()),
+            ],
+        }
+        .build()
+        .unwrap();
+        assert!(cond.check(&log));
+        assert!(!cond.check(&metric));
+        assert_eq!(cond.check_with_context(&log), Ok(()));
+        assert_eq!(
+            cond.check_with_context(&metric),
+            Err("event is a metric, expected log".to_string())
+        );
+
+        let cond = AllConfig {
+            conditions: vec![
+                AnyCondition::Map(Box::new(IsLogConfig {})),
+                AnyCondition::Map(Box::new(IsMetricConfig {})),
+            ],
+        }
+        .build()
+        .unwrap();
+        assert!(!cond.check(&log));
+        assert!(!cond.check(&metric));
+        assert_eq!(
+            cond.check_with_context(&log),
+            Err("event is not a metric type".to_string())
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{AnyCondition, Condition, ConditionConfig, ConditionDescription},
+    config::GenerateConfig,
+    event::Event,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AnyConfig {
+    pub conditions: Vec<AnyCondition>,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<AnyConfig>("any")
+}
+
+impl GenerateConfig for AnyConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"conditions = [{ type = "is_log" }, { type = "is_metric" }]"#).unwrap()
+    }
+}
+
+#[typetag::serde(name = "any")]
+impl ConditionConfig for AnyConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        let conditions = self
+            .conditions
+            .iter()
+            .map(AnyCondition::build)
+            .collect::<crate::Result<_>>()?;
+        Ok(Box::new(Any { conditions }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Passes when at least one inner condition passes, stopping at the first
+/// that does.
+#[derive(Clone)]
+pub struct Any {
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl Condition for Any {
+    fn check(&self, e: &Event) -> bool {
+        self.conditions.iter().any(|condition| condition.check(e))
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        let mut errors = Vec::with_capacity(self.conditions.len());
+        for condition in &self.conditions {
+            match condition.check_with_context(e) {
+                Ok(()) => return Ok(()),
+                Err(error) => errors.push(error),
+            }
+        }
+        Err(format!("no condition matched: {}", errors.join("; ")))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        conditions::{is_log::IsLogConfig, is_metric::IsMetricConfig},
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AnyConfig>();
+    }
+
+    #[test]
+    fn any_basic() {
+        let metric = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let log = Event::from("just a log");
+
+        let cond = AnyConfig {
+            conditions: vec![
+                AnyCondition::Map(Box::new(IsLogConfig {})),
+                AnyCondition::Map(Box::new(IsMetricConfig {})),
+            ],
+        }
+        .build()
+        .unwrap();
+        assert!(cond.check(&log));
+        assert!(cond.check(&metric));
+        assert_eq!(cond.check_with_context(&metric), Ok(()));
+
+        let cond = AnyConfig {
+            conditions: vec![
+                AnyCondition::Map(Box::new(IsMetricConfig {})),
+                AnyCondition::String(r#".message == "something else""#.into()),
+            ],
+        }
+        .build()
+        .unwrap();
+        assert!(!cond.check(&log));
+        assert!(cond.check(&metric));
+        assert_eq!(
+            cond.check_with_context(&log),
+            Err(concat!(
+                "no condition matched: event is not a metric type; ",
+                "source execution resolved to false"
+            )
+            .to_string())
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::Event,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FieldCountConfig {
+    pub max: usize,
+    /// The result for metrics, which have no fields to count.
+    #[serde(default = "crate::serde::default_true")]
+    pub pass_metrics: bool,
+}
+
+impl Default for FieldCountConfig {
+    fn default() -> Self {
+        Self {
+            max: 1000,
+            pass_metrics: true,
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<FieldCountConfig>("field_count")
+}
+
+impl_generate_config_from_default!(FieldCountConfig);
+
+#[typetag::serde(name = "field_count")]
+impl ConditionConfig for FieldCountConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(FieldCount {
+            max: self.max,
+            pass_metrics: self.pass_metrics,
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Passes logs with at most `max` leaf fields. Counting stops once `max` is
+/// exceeded, so large logs aren't traversed in full.
+#[derive(Clone)]
+pub struct FieldCount {
+    max: usize,
+    pass_metrics: bool,
+}
+
+impl FieldCount {
+    fn exceeds(&self, e: &Event) -> Option<bool> {
+        match e {
+            Event::Log(log) => {
+                Some(log.all_fields().take(self.max.saturating_add(1)).count() > self.max)
+            }
+            Event::Metric(_) => None,
+        }
+    }
+}
+
+impl Condition for FieldCount {
+    fn check(&self, e: &Event) -> bool {
+        self.exceeds(e)
+            .map_or(self.pass_metrics, |exceeds| !exceeds)
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match self.exceeds(e) {
+            Some(false) => Ok(()),
+            Some(true) => Err(format!("log has more than {} fields", self.max)),
+            None if self.pass_metrics => Ok(()),
+            None => Err("event is a metric, expected log".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FieldCountConfig>();
+    }
+
+    #[test]
+    fn field_count_basic() {
+        let cond = FieldCountConfig {
+            max: 3,
+            pass_metrics: true,
+        }
+        .build()
+        .unwrap();
+
+        // The message and timestamp count as well.
+        let mut small = Event::from("just a log");
+        assert!(cond.check(&small));
+        assert_eq!(cond.check_with_context(&small), Ok(()));
+
+        // Exactly at the threshold still passes.
+        small.as_mut_log().insert("nested.a", 1);
+        assert!(cond.check(&small));
+
+        let mut large = Event::from("just a log");
+        for i in 0..10 {
+            large.as_mut_log().insert(format!("nested.list[{}]", i), i);
+        }
+        assert!(!cond.check(&large));
+        assert_eq!(
+            cond.check_with_context(&large),
+            Err("log has more than 3 fields".to_string())
+        );
+    }
+
+    #[test]
+    fn field_count_metrics() {
+        let metric = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        let cond = FieldCountConfig::default().build().unwrap();
+        assert!(cond.check(&metric));
+        assert_eq!(cond.check_with_context(&metric), Ok(()));
+
+        let cond = FieldCountConfig {
+            max: 3,
+            pass_metrics: false,
+        }
+        .build()
+        .unwrap();
+        assert!(!cond.check(&metric));
+        assert_eq!(
+            cond.check_with_context(&metric),
+            Err("event is a metric, expected log".to_string())
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use toml::value::Value as TomlValue;
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::{Event, Value},
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FieldEqualsConfig {
+    pub field: String,
+    pub value: TomlValue,
+}
+
+impl Default for FieldEqualsConfig {
+    fn default() -> Self {
+        Self {
+            field: "message".to_string(),
+            value: TomlValue::String("value".to_string()),
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<FieldEqualsConfig>("field_equals")
+}
+
+impl_generate_config_from_default!(FieldEqualsConfig);
+
+#[typetag::serde(name = "field_equals")]
+impl ConditionConfig for FieldEqualsConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(FieldEquals {
+            field: self.field.clone(),
+            value: Value::try_from(self.value.clone())?,
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct FieldEquals {
+    field: String,
+    value: Value,
+}
+
+impl Condition for FieldEquals {
+    fn check(&self, e: &Event) -> bool {
+        match e {
+            Event::Log(log) => log.get(&self.field) == Some(&self.value),
+            Event::Metric(_) => false,
+        }
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match e {
+            Event::Log(log) => match log.get(&self.field) {
+                Some(value) if value == &self.value => Ok(()),
+                Some(value) => Err(format!(
+                    "field {:?} is not equal to {:?}: {:?}",
+                    self.field, self.value, value
+                )),
+                None => Err(format!("field {:?} does not exist", self.field)),
+            },
+            Event::Metric(_) => Err("event is a metric, expected log".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        conditions::AnyCondition,
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FieldEqualsConfig>();
+    }
+
+    #[test]
+    fn field_equals_basic() {
+        let cond = FieldEqualsConfig {
+            field: "nested.service".to_string(),
+            value: TomlValue::String("api".to_string()),
+        }
+        .build()
+        .unwrap();
+
+        let mut matching = Event::from("just a log");
+        matching.as_mut_log().insert("nested.service", "api");
+        assert!(cond.check(&matching));
+        assert_eq!(cond.check_with_context(&matching), Ok(()));
+
+        let mut mismatched = Event::from("just a log");
+        mismatched.as_mut_log().insert("nested.service", "web");
+        assert!(!cond.check(&mismatched));
+        assert!(cond.check_with_context(&mismatched).is_err());
+
+        let missing = Event::from("just a log");
+        assert!(!cond.check(&missing));
+        assert_eq!(
+            cond.check_with_context(&missing),
+            Err(r#"field "nested.service" does not exist"#.to_string())
+        );
+
+        assert!(!cond.check(&Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ))));
+    }
+
+    #[test]
+    fn field_equals_from_config() {
+        let cond = toml::from_str::<AnyCondition>(
+            r#"
+type = "field_equals"
+field = "status"
+value = 200
+"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let mut matching = Event::from("just a log");
+        matching.as_mut_log().insert("status", 200);
+        assert!(cond.check(&matching));
+
+        let mut mismatched = Event::from("just a log");
+        mismatched.as_mut_log().insert("status", "200");
+        assert!(!cond.check(&mismatched));
+    }
+}
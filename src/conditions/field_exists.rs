@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::Event,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FieldExistsConfig {
+    pub field: String,
+}
+
+impl Default for FieldExistsConfig {
+    fn default() -> Self {
+        Self {
+            field: "message".to_string(),
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<FieldExistsConfig>("field_exists")
+}
+
+impl_generate_config_from_default!(FieldExistsConfig);
+
+#[typetag::serde(name = "field_exists")]
+impl ConditionConfig for FieldExistsConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(FieldExists {
+            field: self.field.clone(),
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct FieldExists {
+    field: String,
+}
+
+impl Condition for FieldExists {
+    fn check(&self, e: &Event) -> bool {
+        match e {
+            Event::Log(log) => log.contains(&self.field),
+            Event::Metric(_) => false,
+        }
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match e {
+            Event::Log(log) if log.contains(&self.field) => Ok(()),
+            Event::Log(_) => Err(format!("field {:?} does not exist", self.field)),
+            Event::Metric(_) => Err("event is a metric, expected log".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FieldExistsConfig>();
+    }
+
+    #[test]
+    fn field_exists_basic() {
+        let cond = FieldExistsConfig {
+            field: "nested.items[1].id".to_string(),
+        }
+        .build()
+        .unwrap();
+
+        let mut present = Event::from("just a log");
+        present.as_mut_log().insert("nested.items[1].id", 7);
+        assert!(cond.check(&present));
+        assert_eq!(cond.check_with_context(&present), Ok(()));
+
+        let mut absent = Event::from("just a log");
+        absent.as_mut_log().insert("nested.items[0].id", 7);
+        assert!(!cond.check(&absent));
+        assert_eq!(
+            cond.check_with_context(&absent),
+            Err(r#"field "nested.items[1].id" does not exist"#.to_string())
+        );
+
+        let metric = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        assert!(!cond.check(&metric));
+        assert_eq!(
+            cond.check_with_context(&metric),
+            Err("event is a metric, expected log".to_string())
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::Event,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FieldMatchesConfig {
+    pub field: String,
+    pub pattern: String,
+}
+
+impl Default for FieldMatchesConfig {
+    fn default() -> Self {
+        Self {
+            field: "message".to_string(),
+            pattern: "^.*$".to_string(),
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<FieldMatchesConfig>("field_matches")
+}
+
+impl_generate_config_from_default!(FieldMatchesConfig);
+
+#[typetag::serde(name = "field_matches")]
+impl ConditionConfig for FieldMatchesConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        let regex = Regex::new(&self.pattern)
+            .map_err(|error| format!("Invalid regex \"{}\": {}", self.pattern, error))?;
+        Ok(Box::new(FieldMatches {
+            field: self.field.clone(),
+            regex,
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct FieldMatches {
+    field: String,
+    regex: Regex,
+}
+
+impl Condition for FieldMatches {
+    fn check(&self, e: &Event) -> bool {
+        match e {
+            Event::Log(log) => log
+                .get(&self.field)
+                .map_or(false, |value| self.regex.is_match(&value.to_string_lossy())),
+            Event::Metric(_) => false,
+        }
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match e {
+            Event::Log(log) => match log.get(&self.field) {
+                Some(value) => {
+                    let value = value.to_string_lossy();
+                    if self.regex.is_match(&value) {
+                        Ok(())
+                    } else {
+                        Err(format!(
+                            "field {:?} does not match {:?}: {:?}",
+                            self.field,
+                            self.regex.as_str(),
+                            value
+                        ))
+                    }
+                }
+                None => Err(format!("field {:?} does not exist", self.field)),
+            },
+            Event::Metric(_) => Err("event is a metric, expected log".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FieldMatchesConfig>();
+    }
+
+    #[test]
+    fn field_matches_basic() {
+        let cond = FieldMatchesConfig {
+            field: "nested.path".to_string(),
+            pattern: "^/api/v[0-9]+/".to_string(),
+        }
+        .build()
+        .unwrap();
+
+        let mut matching = Event::from("just a log");
+        matching.as_mut_log().insert("nested.path", "/api/v2/users");
+        assert!(cond.check(&matching));
+        assert_eq!(cond.check_with_context(&matching), Ok(()));
+
+        let mut mismatched = Event::from("just a log");
+        mismatched
+            .as_mut_log()
+            .insert("nested.path", "/static/app.js");
+        assert!(!cond.check(&mismatched));
+        assert_eq!(
+            cond.check_with_context(&mismatched),
+            Err(
+                r#"field "nested.path" does not match "^/api/v[0-9]+/": "/static/app.js""#
+                    .to_string()
+            )
+        );
+
+        let missing = Event::from("just a log");
+        assert!(!cond.check(&missing));
+        assert_eq!(
+            cond.check_with_context(&missing),
+            Err(r#"field "nested.path" does not exist"#.to_string())
+        );
+
+        assert!(!cond.check(&Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ))));
+    }
+
+    #[test]
+    fn field_matches_non_string() {
+        let cond = FieldMatchesConfig {
+            field: "status".to_string(),
+            pattern: "^5[0-9]{2}$".to_string(),
+        }
+        .build()
+        .unwrap();
+
+        let mut event = Event::from("just a log");
+        event.as_mut_log().insert("status", 503);
+        assert!(cond.check(&event));
+    }
+
+    #[test]
+    fn field_matches_invalid_pattern() {
+        let error = FieldMatchesConfig {
+            field: "message".to_string(),
+            pattern: "(unclosed".to_string(),
+        }
+        .build()
+        .err()
+        .unwrap();
+
+        assert!(error
+            .to_string()
+            .starts_with(r#"Invalid regex "(unclosed": "#));
+    }
+}
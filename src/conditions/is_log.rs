@@ -34,10 +34,9 @@ impl Condition for IsLog {
     }
 
     fn check_with_context(&self, e: &Event) -> Result<(), String> {
-        if self.check(e) {
-            Ok(())
-        } else {
-            Err("event is not a log type".to_string())
+        match e {
+            Event::Log(_) => Ok(()),
+            Event::Metric(_) => Err("event is a metric, expected log".to_string()),
         }
     }
 }
@@ -68,4 +67,19 @@ mod test {
             MetricValue::Counter { value: 1.0 },
         ))),);
     }
+
+    #[test]
+    fn is_log_with_context() {
+        let cond = IsLogConfig {}.build().unwrap();
+
+        assert_eq!(cond.check_with_context(&Event::from("just a log")), Ok(()));
+        assert_eq!(
+            cond.check_with_context(&Event::from(Metric::new(
+                "test metric",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))),
+            Err("event is a metric, expected log".to_string())
+        );
+    }
 }
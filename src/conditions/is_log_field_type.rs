@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::{Event, Value},
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Timestamp,
+    Array,
+    Map,
+}
+
+impl FieldType {
+    fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Self::String, Value::Bytes(_))
+                | (Self::Integer, Value::Integer(_))
+                | (Self::Float, Value::Float(_))
+                | (Self::Boolean, Value::Boolean(_))
+                | (Self::Timestamp, Value::Timestamp(_))
+                | (Self::Array, Value::Array(_))
+                | (Self::Map, Value::Map(_))
+        )
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct IsLogFieldTypeConfig {
+    pub field: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+}
+
+impl Default for IsLogFieldTypeConfig {
+    fn default() -> Self {
+        Self {
+            field: "message".to_string(),
+            field_type: FieldType::String,
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<IsLogFieldTypeConfig>("is_log_field_type")
+}
+
+impl_generate_config_from_default!(IsLogFieldTypeConfig);
+
+#[typetag::serde(name = "is_log_field_type")]
+impl ConditionConfig for IsLogFieldTypeConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(IsLogFieldType {
+            field: self.field.clone(),
+            field_type: self.field_type,
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct IsLogFieldType {
+    field: String,
+    field_type: FieldType,
+}
+
+impl Condition for IsLogFieldType {
+    fn check(&self, e: &Event) -> bool {
+        match e {
+            Event::Log(log) => log
+                .get(&self.field)
+                .map_or(false, |value| self.field_type.matches(value)),
+            Event::Metric(_) => false,
+        }
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match e {
+            Event::Log(log) => match log.get(&self.field) {
+                Some(value) if self.field_type.matches(value) => Ok(()),
+                Some(value) => Err(format!(
+                    "field {:?} is not of type {:?}: {:?}",
+                    self.field, self.field_type, value
+                )),
+                None => Err(format!("field {:?} does not exist", self.field)),
+            },
+            Event::Metric(_) => Err("event is a metric, expected log".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<IsLogFieldTypeConfig>();
+    }
+
+    #[test]
+    fn is_log_field_type_basic() {
+        let cond = IsLogFieldTypeConfig {
+            field: "nested.count".to_string(),
+            field_type: FieldType::Integer,
+        }
+        .build()
+        .unwrap();
+
+        let mut matching = Event::from("just a log");
+        matching.as_mut_log().insert("nested.count", 3);
+        assert!(cond.check(&matching));
+        assert_eq!(cond.check_with_context(&matching), Ok(()));
+
+        let mut mismatched = Event::from("just a log");
+        mismatched.as_mut_log().insert("nested.count", "three");
+        assert!(!cond.check(&mismatched));
+        assert!(cond.check_with_context(&mismatched).is_err());
+
+        let missing = Event::from("just a log");
+        assert!(!cond.check(&missing));
+        assert_eq!(
+            cond.check_with_context(&missing),
+            Err(r#"field "nested.count" does not exist"#.to_string())
+        );
+
+        assert!(!cond.check(&Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ))));
+    }
+}
@@ -68,4 +68,22 @@ mod test {
             MetricValue::Counter { value: 1.0 },
         ))),);
     }
+
+    #[test]
+    fn is_metric_with_context() {
+        let cond = IsMetricConfig {}.build().unwrap();
+
+        assert_eq!(
+            cond.check_with_context(&Event::from("just a log")),
+            Err("event is not a metric type".to_string())
+        );
+        assert_eq!(
+            cond.check_with_context(&Event::from(Metric::new(
+                "test metric",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))),
+            Ok(())
+        );
+    }
 }
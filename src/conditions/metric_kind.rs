@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::{metric::MetricKind, Event},
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MetricKindConfig {
+    pub kind: MetricKind,
+}
+
+impl Default for MetricKindConfig {
+    fn default() -> Self {
+        Self {
+            kind: MetricKind::Incremental,
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<MetricKindConfig>("metric_kind")
+}
+
+impl_generate_config_from_default!(MetricKindConfig);
+
+#[typetag::serde(name = "metric_kind")]
+impl ConditionConfig for MetricKindConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(IsMetricKind { kind: self.kind }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct IsMetricKind {
+    kind: MetricKind,
+}
+
+impl Condition for IsMetricKind {
+    fn check(&self, e: &Event) -> bool {
+        matches!(e, Event::Metric(metric) if metric.kind() == self.kind)
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match e {
+            Event::Metric(metric) if metric.kind() == self.kind => Ok(()),
+            Event::Metric(metric) => Err(format!(
+                "metric kind is {:?}, expected {:?}",
+                metric.kind(),
+                self.kind
+            )),
+            Event::Log(_) => Err("event is not a metric type".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricKindConfig>();
+    }
+
+    #[test]
+    fn metric_kind_basic() {
+        let cond = MetricKindConfig {
+            kind: MetricKind::Absolute,
+        }
+        .build()
+        .unwrap();
+        let absolute = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        ));
+        let incremental = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let log = Event::from("just a log");
+
+        assert!(cond.check(&absolute));
+        assert!(!cond.check(&incremental));
+        assert!(!cond.check(&log));
+
+        assert_eq!(cond.check_with_context(&absolute), Ok(()));
+        assert_eq!(
+            cond.check_with_context(&incremental),
+            Err("metric kind is Incremental, expected Absolute".to_string())
+        );
+        assert_eq!(
+            cond.check_with_context(&log),
+            Err("event is not a metric type".to_string())
+        );
+    }
+}
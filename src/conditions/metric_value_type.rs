@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{Condition, ConditionConfig, ConditionDescription},
+    event::{metric::MetricValue, Event},
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricValueType {
+    Counter,
+    Gauge,
+    Set,
+    Distribution,
+    AggregatedHistogram,
+    AggregatedSummary,
+}
+
+impl MetricValueType {
+    fn of(value: &MetricValue) -> Self {
+        match value {
+            MetricValue::Counter { .. } => Self::Counter,
+            MetricValue::Gauge { .. } => Self::Gauge,
+            MetricValue::Set { .. } => Self::Set,
+            MetricValue::Distribution { .. } => Self::Distribution,
+            MetricValue::AggregatedHistogram { .. } => Self::AggregatedHistogram,
+            MetricValue::AggregatedSummary { .. } => Self::AggregatedSummary,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MetricValueTypeConfig {
+    #[serde(rename = "type")]
+    pub value_type: MetricValueType,
+}
+
+impl Default for MetricValueTypeConfig {
+    fn default() -> Self {
+        Self {
+            value_type: MetricValueType::Counter,
+        }
+    }
+}
+
+inventory::submit! {
+    ConditionDescription::new::<MetricValueTypeConfig>("metric_value_type")
+}
+
+impl_generate_config_from_default!(MetricValueTypeConfig);
+
+#[typetag::serde(name = "metric_value_type")]
+impl ConditionConfig for MetricValueTypeConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(IsMetricValueType {
+            value_type: self.value_type,
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct IsMetricValueType {
+    value_type: MetricValueType,
+}
+
+impl Condition for IsMetricValueType {
+    fn check(&self, e: &Event) -> bool {
+        matches!(e, Event::Metric(metric) if MetricValueType::of(metric.value()) == self.value_type)
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        match e {
+            Event::Metric(metric) => {
+                let value_type = MetricValueType::of(metric.value());
+                if value_type == self.value_type {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "metric value type is {:?}, expected {:?}",
+                        value_type, self.value_type
+                    ))
+                }
+            }
+            Event::Log(_) => Err("event is not a metric type".to_string()),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricValueTypeConfig>();
+    }
+
+    #[test]
+    fn metric_value_type_basic() {
+        let cond = MetricValueTypeConfig {
+            value_type: MetricValueType::Counter,
+        }
+        .build()
+        .unwrap();
+        let counter = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let gauge = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        ));
+
+        assert!(cond.check(&counter));
+        assert!(!cond.check(&gauge));
+        assert!(!cond.check(&Event::from("just a log")));
+
+        assert_eq!(cond.check_with_context(&counter), Ok(()));
+        assert_eq!(
+            cond.check_with_context(&gauge),
+            Err("metric value type is Gauge, expected Counter".to_string())
+        );
+    }
+}
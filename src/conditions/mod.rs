@@ -2,11 +2,21 @@ use crate::config::component::ComponentDescription;
 use crate::event::Event;
 use serde::{Deserialize, Serialize};
 
+pub mod all;
+pub mod any;
 pub mod check_fields;
 #[cfg(feature = "transforms-filter")]
 pub mod datadog_search;
+pub mod field_count;
+pub mod field_equals;
+pub mod field_exists;
+pub mod field_matches;
 pub mod is_log;
+pub mod is_log_field_type;
 pub mod is_metric;
+pub mod metric_kind;
+pub mod metric_value_type;
+pub mod not;
 pub mod vrl;
 
 pub use check_fields::CheckFieldsConfig;
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conditions::{AnyCondition, Condition, ConditionConfig, ConditionDescription},
+    config::GenerateConfig,
+    event::Event,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NotConfig {
+    pub condition: AnyCondition,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<NotConfig>("not")
+}
+
+impl GenerateConfig for NotConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"condition.type = "is_log""#).unwrap()
+    }
+}
+
+#[typetag::serde(name = "not")]
+impl ConditionConfig for NotConfig {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(Not {
+            inner: self.condition.build()?,
+        }))
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct Not {
+    inner: Box<dyn Condition>,
+}
+
+impl Condition for Not {
+    fn check(&self, e: &Event) -> bool {
+        !self.inner.check(e)
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        if self.check(e) {
+            Ok(())
+        } else {
+            Err("inner condition matched".to_string())
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        conditions::is_log::IsLogConfig,
+        event::metric::{Metric, MetricKind, MetricValue},
+        event::Event,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NotConfig>();
+    }
+
+    #[test]
+    fn not_is_log() {
+        let cond = NotConfig {
+            condition: AnyCondition::Map(Box::new(IsLogConfig {})),
+        }
+        .build()
+        .unwrap();
+        let metric = Event::from(Metric::new(
+            "test metric",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        assert!(cond.check(&metric));
+        assert!(!cond.check(&Event::from("just a log")));
+
+        assert_eq!(cond.check_with_context(&metric), Ok(()));
+        assert_eq!(
+            cond.check_with_context(&Event::from("just a log")),
+            Err("inner condition matched".to_string())
+        );
+    }
+}
@@ -3,20 +3,20 @@ use metrics::counter;
 use serde_json::Error;
 
 #[derive(Debug)]
-pub(crate) struct MetricToLogFailedSerialize {
+pub(crate) struct LogToMetricReverseFailed {
     pub error: Error,
 }
 
-impl<'a> InternalEvent for MetricToLogFailedSerialize {
+impl InternalEvent for LogToMetricReverseFailed {
     fn emit_logs(&self) {
         warn!(
-            message = "Metric failed to serialize as JSON.",
-            error = ?self.error,
+            message = "Log could not be converted to a metric.",
+            error = %self.error,
             internal_log_rate_secs = 30
         )
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "failed_serialize");
+        counter!("processing_errors_total", 1, "error_type" => "failed_conversion");
     }
 }
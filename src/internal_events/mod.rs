@@ -76,13 +76,13 @@ mod key_value_parser;
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
 mod log_to_metric;
+#[cfg(feature = "transforms-log_to_metric_reverse")]
+mod log_to_metric_reverse;
 #[cfg(feature = "transforms-logfmt_parser")]
 mod logfmt_parser;
 mod logplex;
 #[cfg(feature = "transforms-lua")]
 mod lua;
-#[cfg(feature = "transforms-metric_to_log")]
-mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
 #[cfg(feature = "sinks-nats")]
@@ -215,13 +215,13 @@ pub(crate) use self::key_value_parser::*;
 pub use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
 pub(crate) use self::log_to_metric::*;
+#[cfg(feature = "transforms-log_to_metric_reverse")]
+pub(crate) use self::log_to_metric_reverse::*;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub use self::logfmt_parser::*;
 pub use self::logplex::*;
 #[cfg(feature = "transforms-lua")]
 pub use self::lua::*;
-#[cfg(feature = "transforms-metric_to_log")]
-pub(crate) use self::metric_to_log::*;
 #[cfg(feature = "sinks-nats")]
 pub use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
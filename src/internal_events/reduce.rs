@@ -9,3 +9,27 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct ReduceFlushTicked;
+
+impl InternalEvent for ReduceFlushTicked {
+    fn emit_metrics(&self) {
+        counter!("reduce_flush_ticks_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ReduceByteLimitFlushed {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ReduceByteLimitFlushed {
+    fn emit_logs(&self) {
+        debug!(message = "Flushed reduce group that exceeded max_bytes_per_group.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("byte_limit_events_flushed_total", 1);
+    }
+}
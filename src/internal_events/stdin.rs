@@ -31,3 +31,33 @@ impl InternalEvent for StdinReadFailed {
         counter!("stdin_reads_failed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct StdinJsonDecodeFailed<'a> {
+    pub error: &'a serde_json::Error,
+}
+
+impl<'a> InternalEvent for StdinJsonDecodeFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Unable to decode line as JSON, keeping it as the message.", error = ?self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("decode_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct StdinLineTruncated {
+    pub max_length: usize,
+}
+
+impl InternalEvent for StdinLineTruncated {
+    fn emit_logs(&self) {
+        warn!(message = "Line exceeded max_length and was truncated.", max_length = %self.max_length, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("stdin_lines_truncated_total", 1);
+    }
+}
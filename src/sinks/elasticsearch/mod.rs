@@ -77,6 +77,18 @@ pub struct ElasticSearchConfig {
 }
 
 impl ElasticSearchConfig {
+    /// Metrics are converted with `MetricToLog` directly, so the checks the
+    /// `metric_to_log` transform does when it's built are done here instead.
+    fn validate_metrics(&self) -> crate::Result<()> {
+        if let Some(metrics) = &self.metrics {
+            if metrics.explode_histograms {
+                return Err(ParseError::ExplodeHistogramsUnsupported.into());
+            }
+            metrics.validate()?;
+        }
+        Ok(())
+    }
+
     fn bulk_action(&self) -> crate::Result<Option<Template>> {
         Ok(self
             .normal
@@ -373,6 +385,7 @@ impl SinkConfig for ElasticSearchConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        self.validate_metrics()?;
         let common = ElasticSearchCommon::parse_config(self)?;
         let client = HttpClient::new(common.tls_settings.clone())?;
 
@@ -494,6 +507,8 @@ enum ParseError {
     IndexTemplate { source: TemplateParseError },
     #[snafu(display("Batch action template parse error: {}", source))]
     BatchActionTemplate { source: TemplateParseError },
+    #[snafu(display("Option `metrics.explode_histograms` is not supported by this sink"))]
+    ExplodeHistogramsUnsupported,
 }
 
 impl ElasticSearchCommon {
@@ -666,10 +681,7 @@ impl ElasticSearchCommon {
         request.add_old_option(config.headers.take());
 
         let metric_config = config.metrics.clone().unwrap_or_default();
-        let metric_to_log = MetricToLog::new(
-            metric_config.host_tag,
-            metric_config.timezone.unwrap_or_default(),
-        );
+        let metric_to_log = MetricToLog::new(&metric_config);
 
         Ok(Self {
             authorization,
@@ -974,6 +986,31 @@ mod tests {
             .starts_with(r#"{"gauge":{"value":42.0},"kind":"absolute","name":"cpu","timestamp""#));
     }
 
+    #[test]
+    fn validate_metrics_options() {
+        let config = |metrics| ElasticSearchConfig {
+            endpoint: String::from("https://example.com"),
+            metrics: Some(metrics),
+            ..Default::default()
+        };
+
+        assert!(config(MetricToLogConfig::default())
+            .validate_metrics()
+            .is_ok());
+        assert!(config(MetricToLogConfig {
+            explode_histograms: true,
+            ..Default::default()
+        })
+        .validate_metrics()
+        .is_err());
+        assert!(config(MetricToLogConfig {
+            type_key: Some("metric..type".into()),
+            ..Default::default()
+        })
+        .validate_metrics()
+        .is_err());
+    }
+
     #[test]
     fn decode_bulk_action_error() {
         let config = ElasticSearchConfig {
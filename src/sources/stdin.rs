@@ -1,14 +1,36 @@
 use crate::{
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceDescription},
-    event::Event,
-    internal_events::{StdinEventReceived, StdinReadFailed},
+    encoding_transcode::Decoder,
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        StdinEventReceived, StdinJsonDecodeFailed, StdinLineTruncated, StdinReadFailed,
+    },
+    line_agg::{self, LineAgg},
+    serde::Fields,
     shutdown::ShutdownSignal,
+    sources::util::{EncodingConfig, MultilineConfig},
     Pipeline,
 };
 use bytes::Bytes;
-use futures::{channel::mpsc, executor, FutureExt, SinkExt, StreamExt, TryStreamExt};
+use chrono::Utc;
+use futures::{
+    channel::mpsc,
+    executor, future,
+    stream::{self, BoxStream},
+    FutureExt, SinkExt, StreamExt, TryStreamExt,
+};
 use serde::{Deserialize, Serialize};
-use std::{io, thread};
+use serde_json::Value as JsonValue;
+use std::{
+    convert::TryFrom,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+use toml::value::Value as TomlValue;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
@@ -16,6 +38,33 @@ pub struct StdinConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    /// Where to record the source type. Defaults to the global log schema's
+    /// `source_type_key`; an empty string omits the field entirely.
+    pub source_type_key: Option<String>,
+    pub line_number_key: Option<String>,
+    pub framing: StdinFraming,
+    pub multiline: Option<MultilineConfig>,
+    pub decoding: StdinDecoding,
+    /// Transcodes each line from this charset to UTF-8, replacing malformed
+    /// sequences. Setting `utf-8` gives lossy UTF-8 handling. Framing happens
+    /// on the raw bytes, so only ASCII-compatible charsets split correctly.
+    pub encoding: Option<EncodingConfig>,
+    /// How many lines the reader thread may queue ahead of the pipeline.
+    /// Defaults to 1024.
+    pub buffer_size: Option<usize>,
+    /// Stop reading and shut the source down once this many events have
+    /// been sent.
+    pub max_events: Option<usize>,
+    /// Send one more event after the last line once stdin reaches EOF, with
+    /// `eof_marker_key` set to `true`. The marker counts towards
+    /// `max_events`.
+    pub emit_eof_marker: bool,
+    /// The field that identifies the EOF marker event. Defaults to `eof`.
+    pub eof_marker_key: Option<String>,
+    /// Static fields added to every event, including the EOF marker. They
+    /// are inserted last, so they only replace the message, timestamp or
+    /// other fields set by the source when configured at the same path.
+    pub fields: Option<Fields<TomlValue>>,
 }
 
 impl Default for StdinConfig {
@@ -23,10 +72,50 @@ impl Default for StdinConfig {
         StdinConfig {
             max_length: default_max_length(),
             host_key: None,
+            source_type_key: None,
+            line_number_key: None,
+            framing: StdinFraming::default(),
+            multiline: None,
+            decoding: StdinDecoding::default(),
+            encoding: None,
+            buffer_size: None,
+            max_events: None,
+            emit_eof_marker: false,
+            eof_marker_key: None,
+            fields: None,
         }
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct StdinFraming {
+    /// The byte that separates records on stdin.
+    pub delimiter: u8,
+}
+
+impl Default for StdinFraming {
+    fn default() -> Self {
+        StdinFraming { delimiter: b'\n' }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StdinDecoding {
+    /// Each line is kept as-is in the message field.
+    Bytes,
+    /// Each line is parsed as a JSON object whose top-level fields become
+    /// fields of the event.
+    Json,
+}
+
+impl Default for StdinDecoding {
+    fn default() -> Self {
+        StdinDecoding::Bytes
+    }
+}
+
 fn default_max_length() -> usize {
     bytesize::kib(100u64) as usize
 }
@@ -74,15 +163,68 @@ where
     let host_key = config
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
+    let source_type_key = match config.source_type_key {
+        Some(key) if key.is_empty() => None,
+        Some(key) => Some(key),
+        None => Some(log_schema().source_type_key().to_string()),
+    };
     let hostname = crate::get_hostname().ok();
-
-    let (mut sender, receiver) = mpsc::channel(1024);
+    let line_number_key = config.line_number_key;
+    let delimiter = config.framing.delimiter;
+    let max_length = config.max_length;
+    let decoding = config.decoding;
+    let max_events = config.max_events.unwrap_or(usize::MAX);
+    let eof_marker_key = if config.emit_eof_marker {
+        Some(config.eof_marker_key.unwrap_or_else(|| "eof".to_string()))
+    } else {
+        None
+    };
+    let static_fields = config
+        .fields
+        .map(|fields| {
+            fields
+                .all_fields()
+                .map(|(key, value)| Value::try_from(value).map(|value| (key, value)))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let reached_eof = Arc::new(AtomicBool::new(false));
+    let mut encoding_decoder = config.encoding.map(|e| Decoder::new(e.charset));
+    let line_agg_config = config
+        .multiline
+        .as_ref()
+        .map(line_agg::Config::try_from)
+        .transpose()?;
+
+    let (mut sender, receiver) = mpsc::channel(config.buffer_size.unwrap_or(1024));
 
     // Start the background thread
+    let reader_reached_eof = Arc::clone(&reached_eof);
     thread::spawn(move || {
         info!("Capturing STDIN.");
 
-        for line in stdin.lines() {
+        let mut stdin = stdin;
+        let mut line_number: i64 = 0;
+        loop {
+            let mut buf = Vec::new();
+            let line = match read_line(&mut stdin, delimiter, max_length, &mut buf) {
+                Ok(None) => {
+                    reader_reached_eof.store(true, Ordering::Relaxed);
+                    break;
+                }
+                Ok(Some(truncated)) => {
+                    if truncated {
+                        emit!(StdinLineTruncated { max_length });
+                    } else if delimiter == b'\n' && buf.last() == Some(&b'\r') {
+                        // Match `BufRead::lines`, which also drops the `\r` of a `\r\n` ending.
+                        buf.pop();
+                    }
+                    line_number += 1;
+                    Ok((line_number, buf))
+                }
+                Err(error) => Err(error),
+            };
             if executor::block_on(sender.send(line)).is_err() {
                 // receiver has closed so we should shutdown
                 return;
@@ -94,15 +236,66 @@ where
         let mut out =
             out.sink_map_err(|error| error!(message = "Unable to send event to out.", %error));
 
-        let res = receiver
+        let lines = receiver
             .take_until(shutdown)
             .map_err(|error| emit!(StdinReadFailed { error }))
-            .map_ok(move |line| {
+            .map_ok(move |(line_number, line)| {
+                let line = Bytes::from(line);
+                // transcode each line from the configured charset to utf8
+                let line = match encoding_decoder.as_mut() {
+                    Some(d) => d.decode_to_utf8(line),
+                    None => line,
+                };
+                (line_number, line)
+            })
+            .boxed();
+
+        let lines = match line_agg_config {
+            Some(config) => wrap_with_line_agg(lines, config),
+            None => lines,
+        };
+
+        let eof_marker = {
+            let host_key = host_key.clone();
+            let hostname = hostname.clone();
+            let source_type_key = source_type_key.clone();
+            let static_fields = static_fields.clone();
+            stream::once(async move {
+                // Dropping the sender at EOF is what ended `lines`, so the flag
+                // is already set when this runs.
+                eof_marker_key
+                    .filter(|_| reached_eof.load(Ordering::Relaxed))
+                    .map(|marker_key| {
+                        let mut event =
+                            create_eof_marker(&marker_key, &host_key, &hostname, &source_type_key);
+                        insert_static_fields(&mut event, &static_fields);
+                        Ok(event)
+                    })
+            })
+            .filter_map(future::ready)
+        };
+
+        let res = lines
+            .map_ok(move |(line_number, line)| {
                 emit!(StdinEventReceived {
                     byte_size: line.len()
                 });
-                create_event(Bytes::from(line), &host_key, &hostname)
+                let mut event = create_event(
+                    line,
+                    line_number,
+                    &host_key,
+                    &hostname,
+                    &source_type_key,
+                    &line_number_key,
+                    decoding,
+                );
+                insert_static_fields(&mut event, &static_fields);
+                event
             })
+            .chain(eof_marker)
+            // Ending the stream here drops the receiver, which stops the
+            // reader thread the next time it sends a line.
+            .take(max_events)
             .forward(&mut out)
             .inspect(|_| info!("Finished sending."))
             .await;
@@ -113,25 +306,164 @@ where
     }))
 }
 
-fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event {
-    let mut event = Event::from(line);
+/// Reads the next `delimiter`-terminated line into `buf`, without the
+/// delimiter, keeping at most `max_length` bytes of it. The rest of an
+/// oversized line is consumed and discarded so it never has to be held in
+/// memory. Returns `Ok(None)` at EOF, otherwise whether the line was truncated.
+fn read_line(
+    reader: &mut impl io::BufRead,
+    delimiter: u8,
+    max_length: usize,
+    buf: &mut Vec<u8>,
+) -> io::Result<Option<bool>> {
+    let mut read_any = false;
+    let mut truncated = false;
+
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        if available.is_empty() {
+            break;
+        }
+        read_any = true;
+
+        let (chunk, done) = match available.iter().position(|&b| b == delimiter) {
+            Some(index) => (&available[..index], true),
+            None => (available, false),
+        };
+        let room = max_length.saturating_sub(buf.len());
+        if chunk.len() > room {
+            truncated = true;
+        }
+        buf.extend_from_slice(&chunk[..chunk.len().min(room)]);
+
+        let used = chunk.len() + done as usize;
+        reader.consume(used);
+        if done {
+            break;
+        }
+    }
+
+    if !read_any {
+        return Ok(None);
+    }
+
+    if truncated {
+        // Don't leave half of a multi-byte character at the end.
+        if let Err(error) = std::str::from_utf8(buf) {
+            if error.error_len().is_none() {
+                buf.truncate(error.valid_up_to());
+            }
+        }
+    }
+
+    Ok(Some(truncated))
+}
+
+fn wrap_with_line_agg(
+    lines: BoxStream<'static, Result<(i64, Bytes), ()>>,
+    config: line_agg::Config,
+) -> BoxStream<'static, Result<(i64, Bytes), ()>> {
+    // The aggregator only deals in lines, so a read failure ends the stream
+    // here. Ending the inner stream, whether by EOF, shutdown or error, makes
+    // the aggregator flush whatever it has buffered. An aggregated event
+    // keeps the line number of its first line.
+    let lines = lines
+        .take_while(|line| future::ready(line.is_ok()))
+        .filter_map(|line| {
+            future::ready(line.ok().map(|(line_number, line)| ((), line, line_number)))
+        });
+
+    LineAgg::new(lines, line_agg::Logic::new(config))
+        .map(|((), line, line_number)| Ok((line_number, line)))
+        .boxed()
+}
+
+fn create_event(
+    line: Bytes,
+    line_number: i64,
+    host_key: &str,
+    hostname: &Option<String>,
+    source_type_key: &Option<String>,
+    line_number_key: &Option<String>,
+    decoding: StdinDecoding,
+) -> Event {
+    let mut event = match decoding {
+        StdinDecoding::Bytes => Event::from(line),
+        StdinDecoding::Json => decode_json(line),
+    };
 
     // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("stdin"));
+    if let Some(source_type_key) = &source_type_key {
+        event
+            .as_mut_log()
+            .insert(source_type_key, Bytes::from("stdin"));
+    }
 
     if let Some(hostname) = &hostname {
         event.as_mut_log().insert(host_key, hostname.clone());
     }
 
+    if let Some(line_number_key) = &line_number_key {
+        event.as_mut_log().insert(line_number_key, line_number);
+    }
+
     event
 }
 
+fn create_eof_marker(
+    marker_key: &str,
+    host_key: &str,
+    hostname: &Option<String>,
+    source_type_key: &Option<String>,
+) -> Event {
+    let mut log = LogEvent::default();
+    log.insert(log_schema().timestamp_key(), Utc::now());
+    log.insert(marker_key, true);
+
+    if let Some(source_type_key) = &source_type_key {
+        log.insert(source_type_key, Bytes::from("stdin"));
+    }
+
+    if let Some(hostname) = &hostname {
+        log.insert(host_key, hostname.clone());
+    }
+
+    log.into()
+}
+
+fn insert_static_fields(event: &mut Event, fields: &[(String, Value)]) {
+    let log = event.as_mut_log();
+    for (key, value) in fields {
+        log.insert(key, value.clone());
+    }
+}
+
+fn decode_json(line: Bytes) -> Event {
+    match serde_json::from_slice::<serde_json::Map<String, JsonValue>>(&line) {
+        Ok(fields) => {
+            let mut log = LogEvent::default();
+            log.insert(log_schema().timestamp_key(), Utc::now());
+            for (key, value) in fields {
+                log.insert_flat(key, value);
+            }
+            log.into()
+        }
+        Err(error) => {
+            emit!(StdinJsonDecodeFailed { error: &error });
+            Event::from(line)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{test_util::trace_init, Pipeline};
+    use encoding_rs::{UTF_8, WINDOWS_1252};
     use std::io::Cursor;
 
     #[test]
@@ -145,7 +477,15 @@ mod tests {
         let host_key = "host".to_string();
         let hostname = Some("Some.Machine".to_string());
 
-        let event = create_event(line, &host_key, &hostname);
+        let event = create_event(
+            line,
+            1,
+            &host_key,
+            &hostname,
+            &Some(log_schema().source_type_key().to_string()),
+            &None,
+            StdinDecoding::Bytes,
+        );
         let log = event.into_log();
 
         assert_eq!(log["host"], "Some.Machine".into());
@@ -153,6 +493,46 @@ mod tests {
         assert_eq!(log[log_schema().source_type_key()], "stdin".into());
     }
 
+    #[test]
+    fn stdin_create_event_json() {
+        let line = Bytes::from(r#"{"message":"hello","nested":{"a.b":1},"count":2}"#);
+        let event = create_event(
+            line,
+            1,
+            "host",
+            &None,
+            &Some(log_schema().source_type_key().to_string()),
+            &None,
+            StdinDecoding::Json,
+        );
+        let log = event.into_log();
+
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log["count"], 2.into());
+        assert_eq!(log["nested.a\\.b"], 1.into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+        assert_eq!(log[log_schema().source_type_key()], "stdin".into());
+    }
+
+    #[test]
+    fn stdin_create_event_json_fallback() {
+        for line in &["not json", "[1, 2]"] {
+            let event = create_event(
+                Bytes::from(*line),
+                1,
+                "host",
+                &None,
+                &Some(log_schema().source_type_key().to_string()),
+                &None,
+                StdinDecoding::Json,
+            );
+            let log = event.into_log();
+
+            assert_eq!(log[log_schema().message_key()], (*line).into());
+            assert_eq!(log[log_schema().source_type_key()], "stdin".into());
+        }
+    }
+
     #[tokio::test]
     async fn stdin_decodes_line() {
         trace_init();
@@ -183,4 +563,312 @@ mod tests {
         let event = stream.next().await;
         assert!(event.is_none());
     }
+
+    #[tokio::test]
+    async fn stdin_custom_delimiter() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            framing: StdinFraming { delimiter: 0 },
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("hello\nworld\0hello world again\0");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let events: Vec<_> = rx.collect().await;
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "hello\nworld".into()
+        );
+        assert_eq!(
+            events[1].as_log()[log_schema().message_key()],
+            "hello world again".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn stdin_multiline() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            multiline: Some(MultilineConfig {
+                start_pattern: "^[^\\s]".to_owned(),
+                condition_pattern: "^[\\s]+at".to_owned(),
+                mode: line_agg::Mode::ContinueThrough,
+                timeout_ms: 1000,
+            }),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new(concat!(
+            "java.lang.Exception: boom\n",
+            "    at com.example.Foo.bar(Foo.java:10)\n",
+            "    at com.example.Foo.main(Foo.java:5)\n",
+            "next message",
+        ));
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let messages: Vec<_> = rx
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect()
+            .await;
+        assert_eq!(
+            messages,
+            vec![
+                concat!(
+                    "java.lang.Exception: boom\n",
+                    "    at com.example.Foo.bar(Foo.java:10)\n",
+                    "    at com.example.Foo.main(Foo.java:5)",
+                )
+                .to_owned(),
+                // The trailing partial buffer is flushed at EOF.
+                "next message".to_owned(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stdin_line_numbers() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            line_number_key: Some("line".to_string()),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("one\ntwo\nthree\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let line_numbers: Vec<_> = rx
+            .map(|event| event.as_log()["line"].clone())
+            .collect()
+            .await;
+        assert_eq!(line_numbers, vec![1.into(), 2.into(), 3.into()]);
+    }
+
+    #[tokio::test]
+    async fn stdin_lossy_utf8() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            encoding: Some(EncodingConfig { charset: UTF_8 }),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new(b"hello \xff world\n".to_vec());
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let messages: Vec<_> = rx
+            .map(|event| event.as_log()[log_schema().message_key()].clone())
+            .collect()
+            .await;
+        assert_eq!(messages, vec!["hello \u{fffd} world".into()]);
+    }
+
+    #[tokio::test]
+    async fn stdin_transcodes_charset() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            encoding: Some(EncodingConfig {
+                charset: WINDOWS_1252,
+            }),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new(b"caf\xe9\n".to_vec());
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let messages: Vec<_> = rx
+            .map(|event| event.as_log()[log_schema().message_key()].clone())
+            .collect()
+            .await;
+        assert_eq!(messages, vec!["caf\u{e9}".into()]);
+    }
+
+    #[tokio::test]
+    async fn stdin_source_type_key() {
+        trace_init();
+
+        for (source_type_key, expected) in &[("", None), ("origin", Some("origin"))] {
+            let (tx, rx) = Pipeline::new_test();
+            let config = StdinConfig {
+                source_type_key: Some(source_type_key.to_string()),
+                ..StdinConfig::default()
+            };
+
+            stdin_source(Cursor::new("hello"), config, ShutdownSignal::noop(), tx)
+                .unwrap()
+                .await
+                .unwrap();
+
+            let events: Vec<_> = rx.collect().await;
+            assert_eq!(events.len(), 1);
+            let log = events[0].as_log();
+            assert!(!log.contains(log_schema().source_type_key()));
+            if let Some(key) = expected {
+                assert_eq!(log[*key], "stdin".into());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn stdin_truncates_long_lines() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            max_length: 5,
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("helloworld\nhi\nhell\u{e9}\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let messages: Vec<_> = rx
+            .map(|event| event.as_log()[log_schema().message_key()].clone())
+            .collect()
+            .await;
+        assert_eq!(
+            messages,
+            // "\u{e9}" is two bytes and the limit falls after the first of them.
+            vec!["hello".into(), "hi".into(), "hell".into()]
+        );
+    }
+
+    #[tokio::test]
+    async fn stdin_max_events() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            max_events: Some(2),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("one\ntwo\nthree\nfour\nfive\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let messages: Vec<_> = rx
+            .map(|event| event.as_log()[log_schema().message_key()].clone())
+            .collect()
+            .await;
+        assert_eq!(messages, vec!["one".into(), "two".into()]);
+    }
+
+    #[tokio::test]
+    async fn stdin_eof_marker() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            emit_eof_marker: true,
+            eof_marker_key: Some("done".to_string()),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("one\ntwo\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let events: Vec<_> = rx.collect().await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "one".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+        assert!(!events[1].as_log().contains("done"));
+
+        let marker = events[2].as_log();
+        assert_eq!(marker["done"], true.into());
+        assert!(!marker.contains(log_schema().message_key()));
+        assert_eq!(marker[log_schema().source_type_key()], "stdin".into());
+    }
+
+    #[tokio::test]
+    async fn stdin_static_fields() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = toml::from_str::<StdinConfig>(
+            r#"
+decoding = "json"
+emit_eof_marker = true
+
+[fields]
+env = "prod"
+tenant.id = 42
+"#,
+        )
+        .unwrap();
+        let buf = Cursor::new("{\"message\":\"one\"}\ntwo\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let events: Vec<_> = rx.collect().await;
+        assert_eq!(events.len(), 3);
+        for event in &events {
+            let log = event.as_log();
+            assert_eq!(log["env"], "prod".into());
+            assert_eq!(log["tenant.id"], 42.into());
+        }
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "one".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+        assert_eq!(events[2].as_log()["eof"], true.into());
+    }
+
+    #[tokio::test]
+    async fn stdin_small_buffer() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            buffer_size: Some(0),
+            ..StdinConfig::default()
+        };
+        let input: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+
+        stdin_source(Cursor::new(input), config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let messages: Vec<_> = rx
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect()
+            .await;
+        let expected: Vec<_> = (0..50).map(|i| format!("line {}", i)).collect();
+        assert_eq!(messages, expected);
+    }
 }
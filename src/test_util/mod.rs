@@ -72,6 +72,25 @@ macro_rules! log_event {
     };
 }
 
+/// Builds a metric `Event` from a name, a `MetricKind` variant and a `MetricValue` variant, such as
+/// `metric_event!("requests", Incremental, Counter { value: 1.0 })`. The metric is timestamped
+/// now and has no namespace or tags. `Metric` builder methods listed after the value are applied
+/// in order, as in `metric_event!(..., with_namespace(Some("app")))`.
+#[macro_export]
+macro_rules! metric_event {
+    ($name:expr, $kind:ident, $value:ident { $($fields:tt)* } $(, $method:ident($($arg:expr),*))* $(,)?) => {
+        crate::event::Event::Metric(
+            crate::event::Metric::new(
+                $name,
+                crate::event::MetricKind::$kind,
+                crate::event::MetricValue::$value { $($fields)* },
+            )
+            .with_timestamp(Some(chrono::Utc::now()))
+            $(.$method($($arg),*))*
+        )
+    };
+}
+
 pub fn test_generate_config<T>()
 where
     for<'de> T: GenerateConfig + serde::Deserialize<'de>,
@@ -393,6 +412,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::retry_until;
+    use crate::event::{Event, Metric, MetricKind, MetricValue};
+    use chrono::{TimeZone, Utc};
     use std::{
         sync::{Arc, RwLock},
         time::Duration,
@@ -418,6 +439,44 @@ mod tests {
 
         retry_until(func, Duration::from_millis(10), Duration::from_secs(1)).await;
     }
+
+    #[test]
+    fn metric_event_counter() {
+        let event = metric_event!("requests", Incremental, Counter { value: 1.0 });
+        let metric = event.as_metric();
+
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.namespace(), None);
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 1.0 });
+        assert!(metric.tags().is_none());
+        assert!(metric.timestamp().is_some());
+    }
+
+    #[test]
+    fn metric_event_gauge_with_builders() {
+        let timestamp = Utc.ymd(2021, 7, 1).and_hms(12, 0, 0);
+        let event = metric_event!(
+            "memory_used_bytes",
+            Absolute,
+            Gauge { value: 512.0 },
+            with_namespace(Some("host")),
+            with_timestamp(Some(timestamp)),
+        );
+
+        assert_eq!(
+            event,
+            Event::Metric(
+                Metric::new(
+                    "memory_used_bytes",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 512.0 },
+                )
+                .with_namespace(Some("host"))
+                .with_timestamp(Some(timestamp))
+            )
+        );
+    }
 }
 
 pub struct CountReceiver<T> {
@@ -0,0 +1,221 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
+    },
+    event::{Event, LogEvent, Metric, Value},
+    internal_events::LogToMetricReverseFailed,
+    transforms::{FunctionTransform, Transform},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Rebuilds metrics from logs in the layout produced by the `metric_to_log`
+/// transform with its default settings, making it the inverse of that
+/// transform.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogToMetricReverseConfig {
+    /// The tag the log's host field is moved back into. This should match the
+    /// `host_tag` given to `metric_to_log`.
+    pub host_tag: Option<String>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<LogToMetricReverseConfig>("log_to_metric_reverse")
+}
+
+impl GenerateConfig for LogToMetricReverseConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            host_tag: Some("host-tag".to_string()),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_to_metric_reverse")]
+impl TransformConfig for LogToMetricReverseConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        Ok(Transform::function(LogToMetricReverse::new(self)))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "log_to_metric_reverse"
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogToMetricReverse {
+    host_tag: String,
+}
+
+impl LogToMetricReverse {
+    pub fn new(config: &LogToMetricReverseConfig) -> Self {
+        Self {
+            host_tag: format!(
+                "tags.{}",
+                config
+                    .host_tag
+                    .clone()
+                    .unwrap_or_else(|| log_schema().host_key().to_string())
+            ),
+        }
+    }
+
+    /// Converts the log back into a metric. Logs without a timestamp are given
+    /// the current time. Returns `None` if the log doesn't describe a metric,
+    /// for example because its value type is unknown.
+    pub fn transform_one(&self, mut log: LogEvent) -> Option<Metric> {
+        if let Some(host) = log.remove(log_schema().host_key()) {
+            log.insert(&self.host_tag, host);
+        }
+        let timestamp = match log.remove(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => timestamp,
+            _ => Utc::now(),
+        };
+
+        let (fields, metadata) = log.into_parts();
+        serde_json::to_value(fields)
+            .and_then(serde_json::from_value::<Metric>)
+            .map(|metric| {
+                let (series, data, _) = metric.into_parts();
+                Metric::from_parts(series, data, metadata).with_timestamp(Some(timestamp))
+            })
+            .map_err(|error| emit!(LogToMetricReverseFailed { error }))
+            .ok()
+    }
+}
+
+impl FunctionTransform for LogToMetricReverse {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        if let Some(metric) = self.transform_one(event.into_log()) {
+            output.push(metric.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::MetricValue;
+    use chrono::{offset::TimeZone, DateTime};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogToMetricReverseConfig>();
+    }
+
+    fn ts() -> DateTime<Utc> {
+        Utc.ymd(2018, 11, 14).and_hms_nano(8, 9, 10, 11)
+    }
+
+    #[cfg(feature = "transforms-metric_to_log")]
+    fn tags() -> std::collections::BTreeMap<String, String> {
+        vec![
+            ("host".to_owned(), "localhost".to_owned()),
+            ("some_tag".to_owned(), "some_value".to_owned()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[cfg(feature = "transforms-metric_to_log")]
+    fn round_trip(metric: Metric) -> Option<Metric> {
+        use crate::transforms::{
+            metric_to_log::{MetricToLog, MetricToLogConfig},
+            test::transform_one,
+        };
+
+        let mut to_log = MetricToLog::new(&MetricToLogConfig {
+            host_tag: Some("host".into()),
+            ..Default::default()
+        });
+        let mut to_metric = LogToMetricReverse::new(&LogToMetricReverseConfig {
+            host_tag: Some("host".into()),
+        });
+
+        let log = transform_one(&mut to_log, Event::Metric(metric))?;
+        transform_one(&mut to_metric, log).map(Event::into_metric)
+    }
+
+    #[cfg(feature = "transforms-metric_to_log")]
+    #[test]
+    fn round_trips_metric_to_log() {
+        use crate::event::metric::{MetricKind, StatisticKind};
+
+        let values = vec![
+            MetricValue::Counter { value: 1.0 },
+            MetricValue::Gauge { value: -2.5 },
+            MetricValue::Set {
+                values: vec!["one".into(), "two".into()].into_iter().collect(),
+            },
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 10, 2.0 => 20],
+                statistic: StatisticKind::Summary,
+            },
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 10, 2.0 => 20],
+                count: 30,
+                sum: 50.0,
+            },
+            MetricValue::AggregatedSummary {
+                quantiles: vector_core::quantiles![0.5 => 1.0, 0.99 => 5.0],
+                count: 30,
+                sum: 50.0,
+            },
+        ];
+
+        for value in values {
+            for kind in &[MetricKind::Absolute, MetricKind::Incremental] {
+                let metric = Metric::new("requests", *kind, value.clone())
+                    .with_namespace(Some("vector"))
+                    .with_tags(Some(tags()))
+                    .with_timestamp(Some(ts()));
+                assert_eq!(round_trip(metric.clone()), Some(metric));
+
+                let untagged =
+                    Metric::new("requests", *kind, value.clone()).with_timestamp(Some(ts()));
+                assert_eq!(round_trip(untagged.clone()), Some(untagged));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_timestamp_defaults_to_now() {
+        let mut log = LogEvent::default();
+        log.insert("name", "requests");
+        log.insert("kind", "incremental");
+        log.insert("counter.value", 1.0);
+
+        let before = Utc::now();
+        let metric = LogToMetricReverse::new(&Default::default())
+            .transform_one(log)
+            .unwrap();
+
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 1.0 });
+        assert!(metric.timestamp().unwrap() >= before);
+    }
+
+    #[test]
+    fn unknown_value_type_is_dropped() {
+        let mut log = LogEvent::default();
+        log.insert("name", "requests");
+        log.insert("kind", "absolute");
+        log.insert("timestamp", ts());
+        log.insert("histogram.value", 1.0);
+
+        let metric = LogToMetricReverse::new(&Default::default()).transform_one(log);
+
+        assert_eq!(metric, None);
+    }
+}
@@ -2,21 +2,55 @@ use crate::{
     config::{
         log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
     },
-    event::{self, Event, LogEvent, Metric},
-    internal_events::MetricToLogFailedSerialize,
+    event::{
+        self,
+        metric::{MetricKind, MetricValue, StatisticKind},
+        Event, LogEvent, Metric, PathComponent, PathIter,
+    },
     transforms::{FunctionTransform, Transform},
-    types::Conversion,
 };
 use chrono::Utc;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use shared::TimeZone;
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct MetricToLogConfig {
     pub host_tag: Option<String>,
+    /// The host inserted into logs of metrics without a host tag.
+    pub default_host: Option<String>,
+    /// Deprecated. Metric timestamps always carry their offset, so this has
+    /// no effect on the output; setting it logs a warning at build time.
     pub timezone: Option<TimeZone>,
+    /// Emit one log per bucket (or quantile) for aggregated histograms and
+    /// summaries instead of a single nested log.
+    pub explode_histograms: bool,
+    /// If set, the name of the metric's value type (e.g. `counter`) is
+    /// inserted at this path.
+    pub type_key: Option<String>,
+    /// What to do with metrics that don't carry a timestamp.
+    pub timestamp_missing: TimestampMissing,
+    /// Nest the metric's value object under the metric's name, e.g.
+    /// `requests.counter.value` rather than `counter.value`.
+    pub prefix_with_name: bool,
+    /// Renames fields of the resulting log, keyed by the field's default
+    /// name. Fields not listed keep their name.
+    pub field_map: IndexMap<String, String>,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampMissing {
+    /// Use the time the metric was processed.
+    #[derivative(Default)]
+    Now,
+    /// Drop the metric entirely.
+    Drop,
+    /// Leave the timestamp field out of the log.
+    None,
 }
 
 inventory::submit! {
@@ -27,7 +61,13 @@ impl GenerateConfig for MetricToLogConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             host_tag: Some("host-tag".to_string()),
+            default_host: None,
             timezone: None,
+            explode_histograms: false,
+            type_key: None,
+            timestamp_missing: TimestampMissing::Now,
+            prefix_with_name: false,
+            field_map: IndexMap::new(),
         })
         .unwrap()
     }
@@ -36,11 +76,9 @@ impl GenerateConfig for MetricToLogConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "metric_to_log")]
 impl TransformConfig for MetricToLogConfig {
-    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(MetricToLog::new(
-            self.host_tag.clone(),
-            self.timezone.unwrap_or(globals.timezone),
-        )))
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        self.validate()?;
+        Ok(Transform::function(MetricToLog::new(self)))
     }
 
     fn input_type(&self) -> DataType {
@@ -56,64 +94,308 @@ impl TransformConfig for MetricToLogConfig {
     }
 }
 
+impl MetricToLogConfig {
+    /// Checks the options that `MetricToLog::new` takes as they are, for
+    /// anything embedding this config without building the transform.
+    pub(crate) fn validate(&self) -> crate::Result<()> {
+        if self.timezone.is_some() {
+            warn!("Option `timezone` has been deprecated and has no effect.");
+        }
+
+        if let Some(type_key) = &self.type_key {
+            validate_path("type_key", type_key)?;
+        }
+        for (from, to) in &self.field_map {
+            validate_path("field_map", from)?;
+            validate_path("field_map", to)?;
+        }
+        Ok(())
+    }
+}
+
+/// Rejects paths that the log accessors would fail to parse, which would
+/// otherwise make every insert at them a silent no-op.
+fn validate_path(option: &str, path: &str) -> crate::Result<()> {
+    if PathIter::new(path).any(|component| component == PathComponent::Invalid) {
+        return Err(format!("invalid field path `{}` in `{}`", path, option).into());
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct MetricToLog {
-    timestamp_key: String,
     host_tag: String,
-    timezone: TimeZone,
+    default_host: Option<String>,
+    explode_histograms: bool,
+    type_key: Option<String>,
+    timestamp_missing: TimestampMissing,
+    prefix_with_name: bool,
+    field_map: IndexMap<String, String>,
 }
 
 impl MetricToLog {
-    pub fn new(host_tag: Option<String>, timezone: TimeZone) -> Self {
+    pub fn new(config: &MetricToLogConfig) -> Self {
         Self {
-            timestamp_key: "timestamp".into(),
             host_tag: format!(
                 "tags.{}",
-                host_tag.unwrap_or_else(|| log_schema().host_key().to_string())
+                config
+                    .host_tag
+                    .clone()
+                    .unwrap_or_else(|| log_schema().host_key().to_string())
             ),
-            timezone,
+            default_host: config.default_host.clone(),
+            explode_histograms: config.explode_histograms,
+            type_key: config.type_key.clone(),
+            timestamp_missing: config.timestamp_missing,
+            prefix_with_name: config.prefix_with_name,
+            field_map: config.field_map.clone(),
         }
     }
 
     pub fn transform_one(&self, metric: Metric) -> Option<LogEvent> {
-        serde_json::to_value(&metric)
-            .map_err(|error| emit!(MetricToLogFailedSerialize { error }))
-            .ok()
-            .and_then(|value| match value {
-                Value::Object(object) => {
-                    // TODO: Avoid a clone here
-                    let mut log = LogEvent::new_with_metadata(metric.metadata().clone());
-
-                    for (key, value) in object {
-                        log.insert_flat(key, value);
-                    }
-
-                    let timestamp = log
-                        .remove(&self.timestamp_key)
-                        .and_then(|value| {
-                            Conversion::Timestamp(self.timezone)
-                                .convert(value.into_bytes())
-                                .ok()
-                        })
-                        .unwrap_or_else(|| event::Value::Timestamp(Utc::now()));
-                    log.insert(&log_schema().timestamp_key(), timestamp);
-
-                    if let Some(host) = log.remove_prune(&self.host_tag, true) {
-                        log.insert(&log_schema().host_key(), host);
-                    }
-
-                    Some(log)
+        let mut log = self.to_log(metric)?;
+        self.rename_fields(&mut log);
+        Some(log)
+    }
+
+    /// Converts the metric into a log with every field under its default
+    /// name, before `field_map` is applied.
+    fn to_log(&self, metric: Metric) -> Option<LogEvent> {
+        let metric_type = metric.value().as_name();
+        let (series, data, metadata) = metric.into_parts();
+        let mut log = LogEvent::new_with_metadata(metadata);
+
+        let name = series.name.name;
+        log.insert_flat("name", name.clone());
+        if let Some(namespace) = series.name.namespace {
+            log.insert_flat("namespace", namespace);
+        }
+        if let Some(tags) = series.tags {
+            let tags = tags
+                .into_iter()
+                .map(|(key, value)| (key, event::Value::from(value)))
+                .collect::<BTreeMap<_, _>>();
+            log.insert_flat("tags", tags);
+        }
+        log.insert_flat(
+            "kind",
+            match data.kind {
+                MetricKind::Incremental => "incremental",
+                MetricKind::Absolute => "absolute",
+            },
+        );
+
+        let value = metric_value_to_value(data.value);
+        if self.prefix_with_name {
+            let path = vec![
+                PathComponent::Key(name),
+                PathComponent::Key(metric_type.to_owned()),
+            ];
+            log.insert_path(path, value);
+        } else {
+            log.insert_flat(metric_type, value);
+        }
+
+        let timestamp = match (data.timestamp, self.timestamp_missing) {
+            (Some(timestamp), _) => Some(timestamp),
+            (None, TimestampMissing::Now) => Some(Utc::now()),
+            (None, TimestampMissing::Drop) => return None,
+            (None, TimestampMissing::None) => None,
+        };
+        if let Some(timestamp) = timestamp {
+            log.insert(&log_schema().timestamp_key(), timestamp);
+        }
+
+        if let Some(host) = log.remove_prune(&self.host_tag, true) {
+            log.insert(&log_schema().host_key(), host);
+        } else if let Some(host) = &self.default_host {
+            log.insert(&log_schema().host_key(), host.clone());
+        }
+
+        if let Some(type_key) = &self.type_key {
+            log.insert(type_key, metric_type);
+        }
+
+        Some(log)
+    }
+
+    fn rename_fields(&self, log: &mut LogEvent) {
+        for (from, to) in &self.field_map {
+            if let Some(value) = log.remove(from) {
+                log.insert(to, value);
+            }
+        }
+    }
+
+    /// Converts an aggregated histogram or summary into one log per bucket or
+    /// quantile. Each log carries the name, kind, timestamp and tags of the
+    /// metric along with the bucket's `le` and `count`, or the quantile's
+    /// `quantile` and `value`. Returns `Err` with the metric untouched for any
+    /// other metric type.
+    pub fn explode_one(&self, metric: Metric) -> Result<Vec<LogEvent>, Metric> {
+        let (value_key, rows): (_, Vec<[(&str, event::Value); 2]>) = match metric.value() {
+            MetricValue::AggregatedHistogram { buckets, .. } => (
+                "aggregated_histogram",
+                buckets
+                    .iter()
+                    .map(|bucket| {
+                        [
+                            ("le", bucket.upper_limit.into()),
+                            ("count", (bucket.count as i64).into()),
+                        ]
+                    })
+                    .collect(),
+            ),
+            MetricValue::AggregatedSummary { quantiles, .. } => (
+                "aggregated_summary",
+                quantiles
+                    .iter()
+                    .map(|quantile| {
+                        [
+                            ("quantile", quantile.upper_limit.into()),
+                            ("value", quantile.value.into()),
+                        ]
+                    })
+                    .collect(),
+            ),
+            _ => return Err(metric),
+        };
+
+        let value_key = if self.prefix_with_name {
+            metric.name().to_owned()
+        } else {
+            value_key.to_owned()
+        };
+        let mut base = match self.to_log(metric) {
+            Some(log) => log,
+            None => return Ok(Vec::new()),
+        };
+        // The value is removed under its default name, before `field_map`
+        // could have moved it.
+        base.as_map_mut().remove(&value_key);
+        self.rename_fields(&mut base);
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let mut log = base.clone();
+                for (key, value) in row.iter().cloned() {
+                    log.insert(key, value);
                 }
-                _ => None,
+                log
             })
+            .collect())
     }
 }
 
+/// Builds the same structure that serializing the metric's value to JSON
+/// would, without going through an intermediate `serde_json::Value`.
+fn metric_value_to_value(value: MetricValue) -> event::Value {
+    match value {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+            object(vec![("value", float(value))])
+        }
+        MetricValue::Set { values } => object(vec![(
+            "values",
+            values.into_iter().collect::<Vec<_>>().into(),
+        )]),
+        MetricValue::Distribution { samples, statistic } => {
+            let samples = samples
+                .into_iter()
+                .map(|sample| {
+                    object(vec![
+                        ("value", float(sample.value)),
+                        ("rate", integer(sample.rate)),
+                    ])
+                })
+                .collect();
+            let statistic = match statistic {
+                StatisticKind::Histogram => "histogram",
+                StatisticKind::Summary => "summary",
+            };
+            object(vec![("samples", samples), ("statistic", statistic.into())])
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let buckets = buckets
+                .into_iter()
+                .map(|bucket| {
+                    object(vec![
+                        ("upper_limit", float(bucket.upper_limit)),
+                        ("count", integer(bucket.count)),
+                    ])
+                })
+                .collect();
+            object(vec![
+                ("buckets", buckets),
+                ("count", integer(count)),
+                ("sum", float(sum)),
+            ])
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => {
+            let quantiles = quantiles
+                .into_iter()
+                .map(|quantile| {
+                    object(vec![
+                        ("upper_limit", float(quantile.upper_limit)),
+                        ("value", float(quantile.value)),
+                    ])
+                })
+                .collect();
+            object(vec![
+                ("quantiles", quantiles),
+                ("count", integer(count)),
+                ("sum", float(sum)),
+            ])
+        }
+    }
+}
+
+fn object(fields: Vec<(&str, event::Value)>) -> event::Value {
+    fields
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect::<BTreeMap<_, _>>()
+        .into()
+}
+
+/// JSON has no representation for NaN or infinity, so these have always come
+/// out of this transform as null.
+fn float(value: f64) -> event::Value {
+    if value.is_finite() {
+        event::Value::Float(value)
+    } else {
+        event::Value::Null
+    }
+}
+
+fn integer(value: u32) -> event::Value {
+    event::Value::Integer(value as i64)
+}
+
 impl FunctionTransform for MetricToLog {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        let retval: Option<Event> = self
-            .transform_one(event.into_metric())
-            .map(|log| log.into());
+        let metric = event.into_metric();
+        let metric = if self.explode_histograms {
+            match self.explode_one(metric) {
+                Ok(logs) => {
+                    output.extend(logs.into_iter().map(Event::from));
+                    return;
+                }
+                Err(metric) => metric,
+            }
+        } else {
+            metric
+        };
+
+        let retval: Option<Event> = self.transform_one(metric).map(|log| log.into());
         output.extend(retval.into_iter())
     }
 }
@@ -135,13 +417,27 @@ mod tests {
         crate::test_util::test_generate_config::<MetricToLogConfig>();
     }
 
+    fn config() -> MetricToLogConfig {
+        MetricToLogConfig {
+            host_tag: Some("host".into()),
+            ..Default::default()
+        }
+    }
+
     fn do_transform(metric: Metric) -> Option<LogEvent> {
         let event = Event::Metric(metric);
-        let mut transform = MetricToLog::new(Some("host".into()), Default::default());
+        let mut transform = MetricToLog::new(&config());
 
         transform_one(&mut transform, event).map(|event| event.into_log())
     }
 
+    fn do_transform_many(metric: Metric, config: MetricToLogConfig) -> Vec<LogEvent> {
+        let mut transform = MetricToLog::new(&config);
+        let mut output = Vec::new();
+        transform.transform(&mut output, Event::Metric(metric));
+        output.into_iter().map(Event::into_log).collect()
+    }
+
     fn ts() -> DateTime<Utc> {
         Utc.ymd(2018, 11, 14).and_hms_nano(8, 9, 10, 11)
     }
@@ -374,4 +670,373 @@ mod tests {
         );
         assert_eq!(log.metadata(), &metadata);
     }
+
+    #[test]
+    fn transform_histogram_exploded() {
+        let histo = Metric::new(
+            "histo",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 10, 2.0 => 20],
+                count: 30,
+                sum: 50.0,
+            },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(ts()));
+
+        let logs = do_transform_many(
+            histo,
+            MetricToLogConfig {
+                explode_histograms: true,
+                ..config()
+            },
+        );
+        let collected: Vec<Vec<_>> = logs.iter().map(|log| log.all_fields().collect()).collect();
+
+        assert_eq!(
+            collected,
+            vec![
+                vec![
+                    (String::from("count"), &Value::from(10)),
+                    (String::from("host"), &Value::from("localhost")),
+                    (String::from("kind"), &Value::from("absolute")),
+                    (String::from("le"), &Value::from(1.0)),
+                    (String::from("name"), &Value::from("histo")),
+                    (String::from("tags.some_tag"), &Value::from("some_value")),
+                    (String::from("timestamp"), &Value::from(ts())),
+                ],
+                vec![
+                    (String::from("count"), &Value::from(20)),
+                    (String::from("host"), &Value::from("localhost")),
+                    (String::from("kind"), &Value::from("absolute")),
+                    (String::from("le"), &Value::from(2.0)),
+                    (String::from("name"), &Value::from("histo")),
+                    (String::from("tags.some_tag"), &Value::from("some_value")),
+                    (String::from("timestamp"), &Value::from(ts())),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_summary_exploded() {
+        let summary = Metric::new(
+            "summary",
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles: vector_core::quantiles![50.0 => 10.0, 90.0 => 20.0],
+                count: 30,
+                sum: 50.0,
+            },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(ts()));
+
+        let logs = do_transform_many(
+            summary,
+            MetricToLogConfig {
+                explode_histograms: true,
+                ..config()
+            },
+        );
+        let collected: Vec<Vec<_>> = logs.iter().map(|log| log.all_fields().collect()).collect();
+
+        assert_eq!(
+            collected,
+            vec![
+                vec![
+                    (String::from("host"), &Value::from("localhost")),
+                    (String::from("kind"), &Value::from("absolute")),
+                    (String::from("name"), &Value::from("summary")),
+                    (String::from("quantile"), &Value::from(50.0)),
+                    (String::from("tags.some_tag"), &Value::from("some_value")),
+                    (String::from("timestamp"), &Value::from(ts())),
+                    (String::from("value"), &Value::from(10.0)),
+                ],
+                vec![
+                    (String::from("host"), &Value::from("localhost")),
+                    (String::from("kind"), &Value::from("absolute")),
+                    (String::from("name"), &Value::from("summary")),
+                    (String::from("quantile"), &Value::from(90.0)),
+                    (String::from("tags.some_tag"), &Value::from("some_value")),
+                    (String::from("timestamp"), &Value::from(ts())),
+                    (String::from("value"), &Value::from(20.0)),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_counter_not_exploded() {
+        let counter = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_timestamp(Some(ts()));
+
+        let logs = do_transform_many(
+            counter,
+            MetricToLogConfig {
+                explode_histograms: true,
+                ..config()
+            },
+        );
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["counter.value"], Value::from(1.0));
+    }
+
+    #[test]
+    fn transform_with_type_key() {
+        let config = MetricToLogConfig {
+            type_key: Some("metric_type".into()),
+            ..config()
+        };
+
+        let counter = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let gauge = Metric::new(
+            "gauge",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        );
+        let histo = Metric::new(
+            "histo",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 10, 2.0 => 20],
+                count: 30,
+                sum: 50.0,
+            },
+        );
+
+        for (metric, expected) in vec![
+            (counter, "counter"),
+            (gauge, "gauge"),
+            (histo, "aggregated_histogram"),
+        ] {
+            let logs = do_transform_many(metric, config.clone());
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0]["metric_type"], Value::from(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn type_key_must_be_a_valid_path() {
+        let with_type_key = |type_key: &str| MetricToLogConfig {
+            type_key: Some(type_key.into()),
+            ..config()
+        };
+        let globals = GlobalOptions::default();
+
+        assert!(with_type_key("metric.type").build(&globals).await.is_ok());
+        assert!(with_type_key("metric..type").build(&globals).await.is_err());
+        assert!(with_type_key("metric[type]").build(&globals).await.is_err());
+        assert!(with_type_key("").build(&globals).await.is_err());
+    }
+
+    #[test]
+    fn transform_timestamp_missing() {
+        let counter = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        let logs = do_transform_many(
+            counter.clone(),
+            MetricToLogConfig {
+                timestamp_missing: TimestampMissing::Now,
+                ..config()
+            },
+        );
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0]["timestamp"].as_timestamp().is_some());
+
+        let logs = do_transform_many(
+            counter.clone(),
+            MetricToLogConfig {
+                timestamp_missing: TimestampMissing::Drop,
+                ..config()
+            },
+        );
+        assert!(logs.is_empty());
+
+        let logs = do_transform_many(
+            counter,
+            MetricToLogConfig {
+                timestamp_missing: TimestampMissing::None,
+                ..config()
+            },
+        );
+        assert_eq!(logs.len(), 1);
+        assert!(!logs[0].contains("timestamp"));
+        assert_eq!(logs[0]["counter.value"], Value::from(1.0));
+    }
+
+    #[test]
+    fn transform_prefix_with_name() {
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(ts()));
+
+        let logs = do_transform_many(
+            counter,
+            MetricToLogConfig {
+                prefix_with_name: true,
+                ..config()
+            },
+        );
+        let collected: Vec<_> = logs[0].all_fields().collect();
+
+        assert_eq!(
+            collected,
+            vec![
+                (String::from("host"), &Value::from("localhost")),
+                (String::from("kind"), &Value::from("absolute")),
+                (String::from("name"), &Value::from("requests")),
+                (String::from("requests.counter.value"), &Value::from(1.0)),
+                (String::from("tags.some_tag"), &Value::from("some_value")),
+                (String::from("timestamp"), &Value::from(ts())),
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_default_host() {
+        let counter = || {
+            Metric::new(
+                "counter",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_timestamp(Some(ts()))
+        };
+        let config = MetricToLogConfig {
+            default_host: Some("default-host".into()),
+            ..config()
+        };
+
+        let logs = do_transform_many(counter(), config.clone());
+        let collected: Vec<_> = logs[0].all_fields().collect();
+        assert_eq!(
+            collected,
+            vec![
+                (String::from("counter.value"), &Value::from(1.0)),
+                (String::from("host"), &Value::from("default-host")),
+                (String::from("kind"), &Value::from("absolute")),
+                (String::from("name"), &Value::from("counter")),
+                (String::from("timestamp"), &Value::from(ts())),
+            ]
+        );
+
+        // A host tag takes precedence over the default.
+        let logs = do_transform_many(counter().with_tags(Some(tags())), config);
+        assert_eq!(logs[0]["host"], Value::from("localhost"));
+    }
+
+    #[test]
+    fn transform_with_field_map() {
+        let counter = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_timestamp(Some(ts()));
+
+        let mut field_map = IndexMap::new();
+        field_map.insert("timestamp".to_string(), "@timestamp".to_string());
+        field_map.insert("name".to_string(), "metric_name".to_string());
+        let logs = do_transform_many(
+            counter,
+            MetricToLogConfig {
+                field_map,
+                ..config()
+            },
+        );
+        let collected: Vec<_> = logs[0].all_fields().collect();
+
+        assert_eq!(
+            collected,
+            vec![
+                (String::from("@timestamp"), &Value::from(ts())),
+                (String::from("counter.value"), &Value::from(1.0)),
+                (String::from("kind"), &Value::from("absolute")),
+                (String::from("metric_name"), &Value::from("counter")),
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_exploded_with_field_map() {
+        let histo = Metric::new(
+            "histo",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 10],
+                count: 10,
+                sum: 5.0,
+            },
+        )
+        .with_timestamp(Some(ts()));
+
+        let mut field_map = IndexMap::new();
+        field_map.insert("aggregated_histogram".to_string(), "histogram".to_string());
+        field_map.insert("name".to_string(), "metric_name".to_string());
+        let logs = do_transform_many(
+            histo,
+            MetricToLogConfig {
+                explode_histograms: true,
+                field_map,
+                ..config()
+            },
+        );
+        let collected: Vec<_> = logs[0].all_fields().collect();
+
+        assert_eq!(
+            collected,
+            vec![
+                (String::from("count"), &Value::from(10)),
+                (String::from("kind"), &Value::from("absolute")),
+                (String::from("le"), &Value::from(1.0)),
+                (String::from("metric_name"), &Value::from("histo")),
+                (String::from("timestamp"), &Value::from(ts())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn field_map_must_hold_valid_paths() {
+        let with_field_map = |from: &str, to: &str| {
+            let mut field_map = IndexMap::new();
+            field_map.insert(from.to_string(), to.to_string());
+            MetricToLogConfig {
+                field_map,
+                ..config()
+            }
+        };
+        let globals = GlobalOptions::default();
+
+        assert!(with_field_map("timestamp", "@timestamp")
+            .build(&globals)
+            .await
+            .is_ok());
+        assert!(with_field_map("tags.host", "host..name")
+            .build(&globals)
+            .await
+            .is_err());
+        assert!(with_field_map("tags[", "host")
+            .build(&globals)
+            .await
+            .is_err());
+    }
 }
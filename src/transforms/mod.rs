@@ -32,6 +32,8 @@ pub mod json_parser;
 pub mod key_value_parser;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
+#[cfg(feature = "transforms-log_to_metric_reverse")]
+pub mod log_to_metric_reverse;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub mod logfmt_parser;
 #[cfg(feature = "transforms-lua")]
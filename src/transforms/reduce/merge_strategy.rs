@@ -2,17 +2,108 @@ use crate::event::{LogEvent, Value};
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, convert::TryFrom};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "snake_case")]
+#[serde(try_from = "MergeStrategyConfig", into = "MergeStrategyConfig")]
 pub enum MergeStrategy {
     Discard,
+    /// Leave the field out of the reduced event.
+    Drop,
     Sum,
     Max,
     Min,
     Array,
     Concat,
     ConcatNewline,
+    /// Keep the value from the event with the greatest `by` field.
+    ArgMax {
+        by: String,
+    },
+    /// Keep the value from the event with the least `by` field.
+    ArgMin {
+        by: String,
+    },
+}
+
+impl MergeStrategy {
+    fn name(&self) -> &'static str {
+        match self {
+            MergeStrategy::Discard => "discard",
+            MergeStrategy::Drop => "drop",
+            MergeStrategy::Sum => "sum",
+            MergeStrategy::Max => "max",
+            MergeStrategy::Min => "min",
+            MergeStrategy::Array => "array",
+            MergeStrategy::Concat => "concat",
+            MergeStrategy::ConcatNewline => "concat_newline",
+            MergeStrategy::ArgMax { .. } => "arg_max",
+            MergeStrategy::ArgMin { .. } => "arg_min",
+        }
+    }
+
+    /// The field events are compared by, for strategies that keep the value
+    /// from one of the events.
+    pub fn by(&self) -> Option<&str> {
+        match self {
+            MergeStrategy::ArgMax { by } | MergeStrategy::ArgMin { by } => Some(by),
+            _ => None,
+        }
+    }
+}
+
+/// A merge strategy as written in the config, either by name, such as
+/// `"sum"`, or as a table for strategies that take options, such as
+/// `{ strategy = "arg_max", by = "latency" }`.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum MergeStrategyConfig {
+    Name(String),
+    Table {
+        strategy: String,
+        by: Option<String>,
+    },
+}
+
+impl TryFrom<MergeStrategyConfig> for MergeStrategy {
+    type Error = String;
+
+    fn try_from(config: MergeStrategyConfig) -> Result<Self, Self::Error> {
+        let (name, by) = match config {
+            MergeStrategyConfig::Name(name) => (name, None),
+            MergeStrategyConfig::Table { strategy, by } => (strategy, by),
+        };
+        let strategy = match (name.as_str(), by) {
+            ("arg_max", Some(by)) => return Ok(MergeStrategy::ArgMax { by }),
+            ("arg_min", Some(by)) => return Ok(MergeStrategy::ArgMin { by }),
+            ("arg_max", None) | ("arg_min", None) => {
+                return Err(format!("merge strategy `{}` requires `by`", name))
+            }
+            (_, Some(_)) => return Err(format!("merge strategy `{}` doesn't take `by`", name)),
+            ("discard", None) => MergeStrategy::Discard,
+            ("drop", None) => MergeStrategy::Drop,
+            ("sum", None) => MergeStrategy::Sum,
+            ("max", None) => MergeStrategy::Max,
+            ("min", None) => MergeStrategy::Min,
+            ("array", None) => MergeStrategy::Array,
+            ("concat", None) => MergeStrategy::Concat,
+            ("concat_newline", None) => MergeStrategy::ConcatNewline,
+            (_, None) => return Err(format!("unknown merge strategy `{}`", name)),
+        };
+        Ok(strategy)
+    }
+}
+
+impl From<MergeStrategy> for MergeStrategyConfig {
+    fn from(strategy: MergeStrategy) -> Self {
+        match strategy.by() {
+            Some(by) => MergeStrategyConfig::Table {
+                strategy: strategy.name().to_string(),
+                by: Some(by.to_string()),
+            },
+            None => MergeStrategyConfig::Name(strategy.name().to_string()),
+        }
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -41,6 +132,21 @@ impl ReduceValueMerger for DiscardMerger {
 
 //------------------------------------------------------------------------------
 
+#[derive(Debug, Clone)]
+struct DropMerger;
+
+impl ReduceValueMerger for DropMerger {
+    fn add(&mut self, _v: Value) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, _k: String, _v: &mut LogEvent) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+
 #[derive(Debug, Clone)]
 struct ConcatMerger {
     v: BytesMut,
@@ -64,7 +170,8 @@ impl ReduceValueMerger for ConcatMerger {
             Ok(())
         } else {
             Err(format!(
-                "expected string value, found: '{}'",
+                "expected string value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             ))
         }
@@ -153,7 +260,8 @@ impl ReduceValueMerger for TimestampWindowMerger {
             self.latest = ts
         } else {
             return Err(format!(
-                "expected timestamp value, found: {}",
+                "expected timestamp value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             ));
         }
@@ -215,7 +323,8 @@ impl ReduceValueMerger for AddNumbersMerger {
             },
             _ => {
                 return Err(format!(
-                    "expected numeric value, found: '{}'",
+                    "expected numeric value, found {}: '{}'",
+                    v.kind(),
                     v.to_string_lossy()
                 ));
             }
@@ -276,7 +385,8 @@ impl ReduceValueMerger for MaxNumberMerger {
             }
             _ => {
                 return Err(format!(
-                    "expected numeric value, found: '{}'",
+                    "expected numeric value, found {}: '{}'",
+                    v.kind(),
                     v.to_string_lossy()
                 ));
             }
@@ -337,7 +447,8 @@ impl ReduceValueMerger for MinNumberMerger {
             }
             _ => {
                 return Err(format!(
-                    "expected numeric value, found: '{}'",
+                    "expected numeric value, found {}: '{}'",
+                    v.kind(),
                     v.to_string_lossy()
                 ));
             }
@@ -356,11 +467,182 @@ impl ReduceValueMerger for MinNumberMerger {
 
 //------------------------------------------------------------------------------
 
-pub trait ReduceValueMerger: std::fmt::Debug + Send + Sync {
+/// Keeps the lexicographically greatest string. Strings and numbers aren't
+/// comparable, so `max` on a string field rejects numbers and vice versa.
+#[derive(Debug, Clone)]
+struct MaxBytesMerger {
+    v: Bytes,
+}
+
+impl MaxBytesMerger {
+    fn new(v: Bytes) -> Self {
+        Self { v }
+    }
+}
+
+impl ReduceValueMerger for MaxBytesMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        match v {
+            Value::Bytes(b) => {
+                if b > self.v {
+                    self.v = b;
+                }
+                Ok(())
+            }
+            _ => Err(format!(
+                "expected string value, found {}: '{}'",
+                v.kind(),
+                v.to_string_lossy()
+            )),
+        }
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k, Value::Bytes(self.v));
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Keeps the lexicographically least string, see `MaxBytesMerger`.
+#[derive(Debug, Clone)]
+struct MinBytesMerger {
+    v: Bytes,
+}
+
+impl MinBytesMerger {
+    fn new(v: Bytes) -> Self {
+        Self { v }
+    }
+}
+
+impl ReduceValueMerger for MinBytesMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        match v {
+            Value::Bytes(b) => {
+                if b < self.v {
+                    self.v = b;
+                }
+                Ok(())
+            }
+            _ => Err(format!(
+                "expected string value, found {}: '{}'",
+                v.kind(),
+                v.to_string_lossy()
+            )),
+        }
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k, Value::Bytes(self.v));
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Compares the `by` values of two events, which can both be numbers or both
+/// be strings.
+fn compare_by(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+        (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+        (Value::Bytes(a), Value::Bytes(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Keeps the value from the event whose `by` value compares as `keep` to
+/// those of the other events. The first such event wins ties, and events
+/// without a `by` value never replace one that has it.
+#[derive(Debug, Clone)]
+struct ArgMerger {
+    v: Value,
+    by: Option<Value>,
+    keep: Ordering,
+}
+
+impl ArgMerger {
+    fn new(v: Value, by: Option<Value>, keep: Ordering) -> Self {
+        Self { v, by, keep }
+    }
+}
+
+impl ReduceValueMerger for ArgMerger {
+    fn add(&mut self, _v: Value) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn add_by(&mut self, v: Value, by: Option<&Value>) -> Result<(), String> {
+        let by = match by {
+            Some(by) => by,
+            None => return Ok(()),
+        };
+        let replace = match &self.by {
+            Some(best) => match compare_by(by, best) {
+                Some(ordering) => ordering == self.keep,
+                None => {
+                    return Err(format!(
+                        "expected `by` value comparable to {}, found {}: '{}'",
+                        best.kind(),
+                        by.kind(),
+                        by.to_string_lossy()
+                    ))
+                }
+            },
+            None => true,
+        };
+        if replace {
+            self.v = v;
+            self.by = Some(by.clone());
+        }
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k, self.v);
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+
+pub trait ReduceValueMerger: std::fmt::Debug + Send + Sync + CloneReduceValueMerger {
     fn add(&mut self, v: Value) -> Result<(), String>;
+
+    /// Merges `v` from an event whose value of the strategy's `by` field is
+    /// `by`. Only mergers that compare events by another field use it.
+    fn add_by(&mut self, v: Value, _by: Option<&Value>) -> Result<(), String> {
+        self.add(v)
+    }
+
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String>;
 }
 
+/// Lets boxed mergers be cloned, so that the merged value can be inspected
+/// without consuming the merger.
+pub trait CloneReduceValueMerger {
+    fn clone_box(&self) -> Box<dyn ReduceValueMerger>;
+}
+
+impl<T> CloneReduceValueMerger for T
+where
+    T: ReduceValueMerger + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn ReduceValueMerger> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ReduceValueMerger> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
 impl From<Value> for Box<dyn ReduceValueMerger> {
     fn from(v: Value) -> Self {
         match v {
@@ -376,29 +658,40 @@ impl From<Value> for Box<dyn ReduceValueMerger> {
     }
 }
 
-pub fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceValueMerger>, String> {
+/// Creates the merger for the first value `v` of a field, from an event whose
+/// value of the strategy's `by` field, if it has one, is `by`.
+pub fn get_value_merger_by(
+    v: Value,
+    m: &MergeStrategy,
+    by: Option<&Value>,
+) -> Result<Box<dyn ReduceValueMerger>, String> {
     match m {
         MergeStrategy::Sum => match v {
             Value::Integer(i) => Ok(Box::new(AddNumbersMerger::new(i.into()))),
             Value::Float(f) => Ok(Box::new(AddNumbersMerger::new(f.into()))),
             _ => Err(format!(
-                "expected number value, found: '{}'",
+                "expected number value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             )),
         },
         MergeStrategy::Max => match v {
             Value::Integer(i) => Ok(Box::new(MaxNumberMerger::new(i.into()))),
             Value::Float(f) => Ok(Box::new(MaxNumberMerger::new(f.into()))),
+            Value::Bytes(b) => Ok(Box::new(MaxBytesMerger::new(b))),
             _ => Err(format!(
-                "expected number value, found: '{}'",
+                "expected number or string value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             )),
         },
         MergeStrategy::Min => match v {
             Value::Integer(i) => Ok(Box::new(MinNumberMerger::new(i.into()))),
             Value::Float(f) => Ok(Box::new(MinNumberMerger::new(f.into()))),
+            Value::Bytes(b) => Ok(Box::new(MinBytesMerger::new(b))),
             _ => Err(format!(
-                "expected number value, found: '{}'",
+                "expected number or string value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             )),
         },
@@ -406,19 +699,28 @@ pub fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceVal
             Value::Bytes(b) => Ok(Box::new(ConcatMerger::new(b, ' '))),
             Value::Array(a) => Ok(Box::new(ConcatArrayMerger::new(a))),
             _ => Err(format!(
-                "expected string or array value, found: '{}'",
+                "expected string or array value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             )),
         },
         MergeStrategy::ConcatNewline => match v {
             Value::Bytes(b) => Ok(Box::new(ConcatMerger::new(b, '\n'))),
             _ => Err(format!(
-                "expected string value, found: '{}'",
+                "expected string value, found {}: '{}'",
+                v.kind(),
                 v.to_string_lossy()
             )),
         },
         MergeStrategy::Array => Ok(Box::new(ArrayMerger::new(v))),
         MergeStrategy::Discard => Ok(Box::new(DiscardMerger::new(v))),
+        MergeStrategy::Drop => Ok(Box::new(DropMerger)),
+        MergeStrategy::ArgMax { .. } => {
+            Ok(Box::new(ArgMerger::new(v, by.cloned(), Ordering::Greater)))
+        }
+        MergeStrategy::ArgMin { .. } => {
+            Ok(Box::new(ArgMerger::new(v, by.cloned(), Ordering::Less)))
+        }
     }
 }
 
@@ -432,8 +734,8 @@ mod test {
     fn initial_values() {
         assert!(get_value_merger("foo".into(), &MergeStrategy::Discard).is_ok());
         assert!(get_value_merger("foo".into(), &MergeStrategy::Sum).is_err());
-        assert!(get_value_merger("foo".into(), &MergeStrategy::Max).is_err());
-        assert!(get_value_merger("foo".into(), &MergeStrategy::Min).is_err());
+        assert!(get_value_merger("foo".into(), &MergeStrategy::Max).is_ok());
+        assert!(get_value_merger("foo".into(), &MergeStrategy::Min).is_ok());
         assert!(get_value_merger("foo".into(), &MergeStrategy::Array).is_ok());
         assert!(get_value_merger("foo".into(), &MergeStrategy::Concat).is_ok());
 
@@ -494,12 +796,28 @@ mod test {
         assert!(get_value_merger(json!(null).into(), &MergeStrategy::ConcatNewline).is_err());
     }
 
+    #[test]
+    fn errors_name_value_kind() {
+        let error = get_value_merger(true.into(), &MergeStrategy::Sum)
+            .err()
+            .unwrap();
+        assert_eq!(error, "expected number value, found boolean: 'true'");
+
+        let mut merger = get_value_merger("foo".into(), &MergeStrategy::Concat).unwrap();
+        let error = merger.add(42.into()).unwrap_err();
+        assert_eq!(error, "expected string value, found integer: '42'");
+    }
+
     #[test]
     fn merging_values() {
         assert_eq!(
             merge("foo".into(), "bar".into(), &MergeStrategy::Discard),
             Ok("foo".into())
         );
+        assert_eq!(
+            merge("foo".into(), "bar".into(), &MergeStrategy::Drop),
+            Err("field was not inserted".into())
+        );
         assert_eq!(
             merge("foo".into(), "bar".into(), &MergeStrategy::Array),
             Ok(json!(["foo", "bar"]).into())
@@ -542,6 +860,9 @@ mod test {
             Ok(42.into())
         );
 
+        assert!(merge(42.into(), "foo".into(), &MergeStrategy::Max).is_err());
+        assert!(merge("foo".into(), 42.into(), &MergeStrategy::Min).is_err());
+
         assert_eq!(
             merge(2.1.into(), 2.1.into(), &MergeStrategy::Sum),
             Ok(4.2.into())
@@ -573,12 +894,84 @@ mod test {
         );
     }
 
+    #[test]
+    fn lexicographic_max_and_min() {
+        let extremum = |strategy| {
+            let mut merger = get_value_merger("a".into(), &strategy).unwrap();
+            merger.add("c".into()).unwrap();
+            merger.add("b".into()).unwrap();
+            let mut output = Event::new_empty_log();
+            merger
+                .insert_into("out".into(), output.as_mut_log())
+                .unwrap();
+            output.as_mut_log().remove("out").unwrap()
+        };
+
+        assert_eq!(extremum(MergeStrategy::Max), "c".into());
+        assert_eq!(extremum(MergeStrategy::Min), "a".into());
+    }
+
+    #[test]
+    fn arg_max_and_min() {
+        let by_strategy = |strategy| {
+            let mut merger =
+                get_value_merger_by("first".into(), &strategy, Some(&20.into())).unwrap();
+            merger.add_by("peak".into(), Some(&35.into())).unwrap();
+            merger.add_by("trough".into(), Some(&5.5.into())).unwrap();
+            merger.add_by("tie".into(), Some(&35.into())).unwrap();
+            merger.add_by("missing".into(), None).unwrap();
+            assert!(merger.add_by("string".into(), Some(&"40".into())).is_err());
+            let mut output = Event::new_empty_log();
+            merger
+                .insert_into("out".into(), output.as_mut_log())
+                .unwrap();
+            output.as_mut_log().remove("out").unwrap()
+        };
+
+        let by = "latency".to_string();
+        assert_eq!(
+            by_strategy(MergeStrategy::ArgMax { by: by.clone() }),
+            "peak".into()
+        );
+        assert_eq!(by_strategy(MergeStrategy::ArgMin { by }), "trough".into());
+    }
+
+    #[test]
+    fn parse_strategies() {
+        #[derive(Deserialize)]
+        struct Config {
+            strategy: MergeStrategy,
+        }
+        let parse = |s: &str| {
+            toml::from_str::<Config>(s).map(|config| MergeStrategyConfig::from(config.strategy))
+        };
+
+        assert!(matches!(
+            parse(r#"strategy = "concat_newline""#),
+            Ok(MergeStrategyConfig::Name(name)) if name == "concat_newline"
+        ));
+        assert!(matches!(
+            parse(r#"strategy = { strategy = "arg_max", by = "latency" }"#),
+            Ok(MergeStrategyConfig::Table { strategy, by: Some(by) })
+                if strategy == "arg_max" && by == "latency"
+        ));
+        assert!(parse(r#"strategy = "arg_min""#).is_err());
+        assert!(parse(r#"strategy = { strategy = "sum", by = "latency" }"#).is_err());
+        assert!(parse(r#"strategy = "median""#).is_err());
+    }
+
+    fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceValueMerger>, String> {
+        get_value_merger_by(v, m, None)
+    }
+
     fn merge(initial: Value, additional: Value, strategy: &MergeStrategy) -> Result<Value, String> {
         let mut merger = get_value_merger(initial, strategy)?;
         merger.add(additional)?;
         let mut output = Event::new_empty_log();
         let mut output = output.as_mut_log();
         merger.insert_into("out".into(), &mut output)?;
-        Ok(output.remove("out").unwrap())
+        output
+            .remove("out")
+            .ok_or_else(|| "field was not inserted".into())
     }
 }
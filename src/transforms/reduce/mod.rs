@@ -1,16 +1,19 @@
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
-    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
-    internal_events::ReduceStaleEventFlushed,
+    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent, Value},
+    internal_events::{
+        ReduceByteLimitFlushed, ReduceFlushTicked, ReduceStaleEventFlushed, TemplateRenderingFailed,
+    },
+    template::Template,
     transforms::{TaskTransform, Transform},
 };
 use async_stream::stream;
 use futures::{stream, Stream, StreamExt};
-use indexmap::IndexMap;
+use indexmap::{map, IndexMap};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{hash_map, HashMap},
+    collections::{hash_map, BTreeMap, HashMap},
     pin::Pin,
     time::{Duration, Instant},
 };
@@ -28,18 +31,77 @@ pub struct ReduceConfig {
 
     pub flush_period_ms: Option<u64>,
 
+    /// If set, a reduce is flushed once it is this old, however recently
+    /// events were merged into it, so that each reduce covers at most one
+    /// window. Also accepted as `max_lifetime_ms`, as it caps how long a
+    /// reduce can live.
+    #[serde(alias = "max_lifetime_ms")]
+    pub window_ms: Option<u64>,
+
     /// An ordered list of fields to distinguish reduces by. Each
     /// reduce has a separate event merging state.
     #[serde(default)]
     pub group_by: Vec<String>,
 
+    /// Whether the `group_by` fields are removed from reduced events.
+    pub drop_group_by_fields: bool,
+
+    /// A template rendered for each event to distinguish reduces by, as an
+    /// alternative to `group_by`.
+    pub group_by_template: Option<Template>,
+
     #[serde(default)]
     pub merge_strategies: IndexMap<String, MergeStrategy>,
 
+    /// The merge strategy for fields not listed in `merge_strategies`,
+    /// applied as if they were listed. When unset, the type of a field's
+    /// first value decides how it is merged.
+    pub default_merge_strategy: Option<MergeStrategy>,
+
     /// An optional condition that determines when an event is the end of a
     /// reduce.
     pub ends_when: Option<AnyCondition>,
+    /// Conditions that determine when an event is the end of a reduce,
+    /// as an alternative to `ends_when`. The condition for an event is the
+    /// one keyed by its value of `ends_when_by_field`, and events without a
+    /// matching key don't end their reduce.
+    pub ends_when_by: IndexMap<String, AnyCondition>,
+    pub ends_when_by_field: Option<String>,
+    /// What `ends_when` is checked against.
+    pub ends_when_on: EndsWhenOn,
+    /// End a reduce, before merging the event, whenever the value of this
+    /// field differs from that of the events already in the reduce.
+    pub ends_when_field_changes: Option<String>,
     pub starts_when: Option<AnyCondition>,
+
+    /// Flush a reduce as soon as the estimated size of the values merged
+    /// into it exceeds this many bytes.
+    pub max_bytes_per_group: Option<usize>,
+
+    /// If set, the reason a reduce was flushed is inserted into the reduced
+    /// event at this path.
+    pub reason_key: Option<String>,
+
+    /// Whether each incoming event is also emitted unchanged, apart from
+    /// the `reduce_original` marker field, next to the reduced events.
+    pub passthrough_originals: bool,
+}
+
+/// The field set on events emitted by `passthrough_originals`.
+const ORIGINAL_MARKER_KEY: &str = "reduce_original";
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EndsWhenOn {
+    /// Check each incoming event, before it is merged.
+    #[derivative(Default)]
+    Event,
+    /// Check the merged state of the reduce, after the incoming event was
+    /// merged into it, so that a reduce can end on an accumulated value.
+    /// The merged event is rebuilt for every incoming event, which costs as
+    /// much as the values accumulated so far.
+    State,
 }
 
 inventory::submit! {
@@ -68,23 +130,63 @@ impl TransformConfig for ReduceConfig {
     }
 }
 
+/// Why a reduce was flushed, as recorded under `reason_key`.
+#[derive(Clone, Copy, Debug)]
+enum FlushReason {
+    EndsWhen,
+    StartsWhen,
+    Expired,
+    Window,
+    FieldChanged,
+    Shutdown,
+    MaxBytes,
+}
+
+impl FlushReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            FlushReason::EndsWhen => "ends_when",
+            FlushReason::StartsWhen => "starts_when",
+            FlushReason::Expired => "expired",
+            FlushReason::Window => "window",
+            FlushReason::FieldChanged => "field_changed",
+            FlushReason::Shutdown => "shutdown",
+            FlushReason::MaxBytes => "max_bytes_per_group",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct ReduceState {
-    fields: HashMap<String, Box<dyn ReduceValueMerger>>,
+    /// The merged fields, in the order they were first seen, so that they
+    /// are inserted into the flushed event deterministically.
+    fields: IndexMap<String, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
+    created_at: Instant,
     metadata: EventMetadata,
+    byte_size: usize,
+    /// The value of `ends_when_field_changes` shared by the merged events.
+    tracked_value: Option<Value>,
 }
 
 impl ReduceState {
-    fn new(e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
+    fn new(
+        e: LogEvent,
+        strategies: &IndexMap<String, MergeStrategy>,
+        default_strategy: Option<&MergeStrategy>,
+    ) -> Self {
+        let by_values = by_values(&e, strategies, default_strategy);
         let (fields, metadata) = e.into_parts();
+        let byte_size = fields_byte_size(&fields);
         Self {
             stale_since: Instant::now(),
-            fields: fields
+            created_at: Instant::now(),
+            fields: merged_fields(fields, strategies)
                 .into_iter()
                 .filter_map(|(k, v)| {
-                    if let Some(strat) = strategies.get(&k) {
-                        match get_value_merger(v, strat) {
+                    if let Some(strat) = find_strategy(&k, strategies, default_strategy) {
+                        let by = strat.by().and_then(|by| by_values.get(by));
+                        match get_value_merger_by(v, strat, by) {
                             Ok(m) => Some((k, m)),
                             Err(error) => {
                                 warn!(message = "Failed to create merger.", field = ?k, %error);
@@ -97,19 +199,31 @@ impl ReduceState {
                 })
                 .collect(),
             metadata,
+            byte_size,
+            tracked_value: None,
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+    fn add_event(
+        &mut self,
+        e: LogEvent,
+        strategies: &IndexMap<String, MergeStrategy>,
+        default_strategy: Option<&MergeStrategy>,
+    ) {
+        let by_values = by_values(&e, strategies, default_strategy);
         let (fields, metadata) = e.into_parts();
         self.metadata.merge(metadata);
+        self.byte_size += fields_byte_size(&fields);
 
-        for (k, v) in fields.into_iter() {
-            let strategy = strategies.get(&k);
+        for (k, v) in merged_fields(fields, strategies) {
+            let strategy = find_strategy(&k, strategies, default_strategy);
+            let by = strategy
+                .and_then(|strat| strat.by())
+                .and_then(|by| by_values.get(by));
             match self.fields.entry(k) {
-                hash_map::Entry::Vacant(entry) => {
+                map::Entry::Vacant(entry) => {
                     if let Some(strat) = strategy {
-                        match get_value_merger(v, strat) {
+                        match get_value_merger_by(v, strat, by) {
                             Ok(m) => {
                                 entry.insert(m);
                             }
@@ -118,11 +232,11 @@ impl ReduceState {
                             }
                         }
                     } else {
-                        entry.insert(v.clone().into());
+                        entry.insert(v.into());
                     }
                 }
-                hash_map::Entry::Occupied(mut entry) => {
-                    if let Err(error) = entry.get_mut().add(v.clone()) {
+                map::Entry::Occupied(mut entry) => {
+                    if let Err(error) = entry.get_mut().add_by(v, by) {
                         warn!(message = "Failed to merge value.", %error);
                     }
                 }
@@ -131,27 +245,159 @@ impl ReduceState {
         self.stale_since = Instant::now();
     }
 
-    fn flush(mut self) -> LogEvent {
+    /// Builds the event this state would flush into, without consuming it.
+    /// Every merger is cloned, so this is linear in the size of the reduce.
+    fn merged_event(&self) -> LogEvent {
+        let mut event = LogEvent::default();
+        for (k, v) in &self.fields {
+            if let Err(error) = v.clone().insert_into(k.clone(), &mut event) {
+                warn!(message = "Failed to merge values for field.", %error);
+            }
+        }
+        event
+    }
+
+    fn exceeds(&self, max_bytes: Option<usize>) -> bool {
+        max_bytes.map_or(false, |max_bytes| self.byte_size > max_bytes)
+    }
+
+    fn flush(
+        mut self,
+        reason_key: Option<&str>,
+        drop_fields: &[String],
+        reason: FlushReason,
+    ) -> LogEvent {
         let mut event = LogEvent::new_with_metadata(self.metadata);
-        for (k, v) in self.fields.drain() {
+        for (k, v) in self.fields.drain(..) {
             if let Err(error) = v.insert_into(k, &mut event) {
                 warn!(message = "Failed to merge values for field.", %error);
             }
         }
+        for field in drop_fields {
+            event.remove(field);
+        }
+        if let Some(reason_key) = reason_key {
+            event.insert(reason_key, reason.as_str());
+        }
         event
     }
 }
 
+/// Returns the prefix matched by a `merge_strategies` key such as
+/// `metrics.*`, including the trailing period.
+fn strategy_prefix(key: &str) -> Option<&str> {
+    if key.ends_with(".*") {
+        key.strip_suffix('*')
+    } else {
+        None
+    }
+}
+
+/// Finds the merge strategy for the field at `path`. A strategy for the exact
+/// path wins over prefix patterns, of which the longest match wins.
+fn find_strategy<'a>(
+    path: &str,
+    strategies: &'a IndexMap<String, MergeStrategy>,
+    default_strategy: Option<&'a MergeStrategy>,
+) -> Option<&'a MergeStrategy> {
+    strategies
+        .get(path)
+        .or_else(|| {
+            strategies
+                .iter()
+                .filter_map(|(key, strategy)| strategy_prefix(key).map(|prefix| (prefix, strategy)))
+                .filter(|(prefix, _)| path.starts_with(prefix))
+                .max_by_key(|(prefix, _)| prefix.len())
+                .map(|(_, strategy)| strategy)
+        })
+        .or(default_strategy)
+}
+
+/// Splits the top level fields of an event into the fields that are merged
+/// separately. Maps are descended into for as long as a prefix pattern lies
+/// below them, so that with `metrics.*` every field under `metrics` is merged
+/// on its own.
+fn merged_fields(
+    fields: BTreeMap<String, Value>,
+    strategies: &IndexMap<String, MergeStrategy>,
+) -> Vec<(String, Value)> {
+    fn descend(path: String, value: Value, prefixes: &[&str], output: &mut Vec<(String, Value)>) {
+        match value {
+            Value::Map(map)
+                if prefixes.iter().any(|prefix| {
+                    prefix
+                        .strip_prefix(path.as_str())
+                        .map_or(false, |rest| rest.starts_with('.'))
+                }) =>
+            {
+                for (key, value) in map {
+                    let path = format!("{}.{}", path, key.replace(".", "\\."));
+                    descend(path, value, prefixes, output);
+                }
+            }
+            value => output.push((path, value)),
+        }
+    }
+
+    let prefixes = strategies
+        .keys()
+        .filter_map(|key| strategy_prefix(key))
+        .collect::<Vec<_>>();
+    if prefixes.is_empty() {
+        return fields.into_iter().collect();
+    }
+
+    let mut output = Vec::with_capacity(fields.len());
+    for (key, value) in fields {
+        descend(key, value, &prefixes, &mut output);
+    }
+    output
+}
+
+/// Takes the values of the fields that `arg_max` and `arg_min` strategies
+/// compare events by, before the event is split into its merged fields.
+fn by_values(
+    event: &LogEvent,
+    strategies: &IndexMap<String, MergeStrategy>,
+    default_strategy: Option<&MergeStrategy>,
+) -> HashMap<String, Value> {
+    strategies
+        .values()
+        .chain(default_strategy)
+        .filter_map(MergeStrategy::by)
+        .filter_map(|by| event.get(by).map(|value| (by.to_string(), value.clone())))
+        .collect()
+}
+
+fn fields_byte_size(fields: &BTreeMap<String, Value>) -> usize {
+    fields
+        .iter()
+        .map(|(k, v)| k.len() + v.byte_size_estimate())
+        .sum()
+}
+
 //------------------------------------------------------------------------------
 
 pub struct Reduce {
     expire_after: Duration,
     flush_period: Duration,
+    window: Option<Duration>,
     group_by: Vec<String>,
+    group_by_template: Option<Template>,
+    /// Fields removed from reduced events, after the discriminant was taken.
+    drop_fields: Vec<String>,
     merge_strategies: IndexMap<String, MergeStrategy>,
+    default_merge_strategy: Option<MergeStrategy>,
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Box<dyn Condition>>,
+    ends_when_by: IndexMap<String, Box<dyn Condition>>,
+    ends_when_by_field: Option<String>,
+    ends_when_on: EndsWhenOn,
+    ends_when_field_changes: Option<String>,
     starts_when: Option<Box<dyn Condition>>,
+    max_bytes_per_group: Option<usize>,
+    reason_key: Option<String>,
+    passthrough_originals: bool,
 }
 
 impl Reduce {
@@ -159,90 +405,308 @@ impl Reduce {
         if config.ends_when.is_some() && config.starts_when.is_some() {
             return Err("only one of `ends_when` and `starts_when` can be provided".into());
         }
+        if !config.ends_when_by.is_empty() {
+            if config.ends_when.is_some() {
+                return Err("only one of `ends_when` and `ends_when_by` can be provided".into());
+            }
+            if config.starts_when.is_some() {
+                return Err("only one of `ends_when_by` and `starts_when` can be provided".into());
+            }
+            if config.ends_when_by_field.is_none() {
+                return Err("`ends_when_by` requires `ends_when_by_field`".into());
+            }
+        }
+        if !config.group_by.is_empty() && config.group_by_template.is_some() {
+            return Err("only one of `group_by` and `group_by_template` can be provided".into());
+        }
 
         let ends_when = config.ends_when.as_ref().map(|c| c.build()).transpose()?;
+        let ends_when_by = config
+            .ends_when_by
+            .iter()
+            .map(|(value, c)| c.build().map(|c| (value.clone(), c)))
+            .collect::<crate::Result<_>>()?;
         let starts_when = config.starts_when.as_ref().map(|c| c.build()).transpose()?;
-        let group_by = config.group_by.clone().into_iter().collect();
+        let group_by: Vec<String> = config.group_by.clone().into_iter().collect();
+        let drop_fields = if config.drop_group_by_fields {
+            group_by.clone()
+        } else {
+            Vec::new()
+        };
 
         Ok(Reduce {
             expire_after: Duration::from_millis(config.expire_after_ms.unwrap_or(30000)),
             flush_period: Duration::from_millis(config.flush_period_ms.unwrap_or(1000)),
+            window: config.window_ms.map(Duration::from_millis),
             group_by,
+            group_by_template: config.group_by_template.clone(),
+            drop_fields,
             merge_strategies: config.merge_strategies.clone(),
+            default_merge_strategy: config.default_merge_strategy.clone(),
             reduce_merge_states: HashMap::new(),
             ends_when,
+            ends_when_by,
+            ends_when_by_field: config.ends_when_by_field.clone(),
+            ends_when_on: config.ends_when_on,
+            ends_when_field_changes: config.ends_when_field_changes.clone(),
             starts_when,
+            max_bytes_per_group: config.max_bytes_per_group,
+            reason_key: config.reason_key.clone(),
+            passthrough_originals: config.passthrough_originals,
         })
     }
 
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let mut flush_discriminants = Vec::new();
         for (k, t) in &self.reduce_merge_states {
-            if t.stale_since.elapsed() >= self.expire_after {
-                flush_discriminants.push(k.clone());
+            if matches!(self.window, Some(window) if t.created_at.elapsed() >= window) {
+                flush_discriminants.push((k.clone(), FlushReason::Window));
+            } else if t.stale_since.elapsed() >= self.expire_after {
+                flush_discriminants.push((k.clone(), FlushReason::Expired));
             }
         }
-        for k in &flush_discriminants {
-            if let Some(t) = self.reduce_merge_states.remove(k) {
-                emit!(ReduceStaleEventFlushed);
-                output.push(Event::from(t.flush()));
+        for (k, reason) in flush_discriminants {
+            if let Some(t) = self.reduce_merge_states.remove(&k) {
+                if let FlushReason::Expired = reason {
+                    emit!(ReduceStaleEventFlushed);
+                }
+                output.push(Event::from(t.flush(
+                    self.reason_key.as_deref(),
+                    &self.drop_fields,
+                    reason,
+                )));
             }
         }
     }
 
     fn flush_all_into(&mut self, output: &mut Vec<Event>) {
-        self.reduce_merge_states
-            .drain()
-            .for_each(|(_, s)| output.push(Event::from(s.flush())));
+        let reason_key = self.reason_key.as_deref();
+        let drop_fields = &self.drop_fields;
+        self.reduce_merge_states.drain().for_each(|(_, s)| {
+            output.push(Event::from(s.flush(
+                reason_key,
+                drop_fields,
+                FlushReason::Shutdown,
+            )))
+        });
     }
 
-    fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
+    fn discriminant(&self, event: &LogEvent) -> Discriminant {
+        match &self.group_by_template {
+            Some(template) => Discriminant::from_value(
+                template
+                    .render(event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingFailed {
+                            error,
+                            field: Some("group_by_template"),
+                            drop_event: false,
+                        })
+                    })
+                    .ok()
+                    .map(Value::from),
+            ),
+            None => Discriminant::from_log_event(event, &self.group_by),
+        }
+    }
+
+    fn push_or_new_reduce_state(
+        &mut self,
+        output: &mut Vec<Event>,
+        event: LogEvent,
+        discriminant: Discriminant,
+    ) {
+        let tracked_value = self
+            .ends_when_field_changes
+            .as_ref()
+            .and_then(|field| event.get(field).cloned());
         match self.reduce_merge_states.entry(discriminant) {
             hash_map::Entry::Vacant(entry) => {
-                entry.insert(ReduceState::new(event, &self.merge_strategies));
+                let mut state = ReduceState::new(
+                    event,
+                    &self.merge_strategies,
+                    self.default_merge_strategy.as_ref(),
+                );
+                state.tracked_value = tracked_value;
+                if state.exceeds(self.max_bytes_per_group) {
+                    emit!(ReduceByteLimitFlushed {
+                        byte_size: state.byte_size
+                    });
+                    output.push(
+                        state
+                            .flush(
+                                self.reason_key.as_deref(),
+                                &self.drop_fields,
+                                FlushReason::MaxBytes,
+                            )
+                            .into(),
+                    );
+                } else {
+                    entry.insert(state);
+                }
             }
             hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().add_event(event, &self.merge_strategies);
+                entry.get_mut().add_event(
+                    event,
+                    &self.merge_strategies,
+                    self.default_merge_strategy.as_ref(),
+                );
+                if entry.get().exceeds(self.max_bytes_per_group) {
+                    let state = entry.remove();
+                    emit!(ReduceByteLimitFlushed {
+                        byte_size: state.byte_size
+                    });
+                    output.push(
+                        state
+                            .flush(
+                                self.reason_key.as_deref(),
+                                &self.drop_fields,
+                                FlushReason::MaxBytes,
+                            )
+                            .into(),
+                    );
+                }
             }
         }
     }
 
     fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        // Flush expired reduces before this event is merged, so that a stale
+        // reduce is never revived by a late event. This matters in
+        // particular with `starts_when`, where events keep landing in the
+        // same reduce until a start event arrives.
+        self.flush_into(output);
+
+        if self.passthrough_originals {
+            let mut original = event.clone();
+            original.as_mut_log().insert(ORIGINAL_MARKER_KEY, true);
+            output.push(original);
+        }
+
         let starts_here = self
             .starts_when
             .as_ref()
             .map(|c| c.check(&event))
             .unwrap_or(false);
-        let ends_here = self
-            .ends_when
-            .as_ref()
-            .map(|c| c.check(&event))
-            .unwrap_or(false);
+        let ends_here = self.ends_when_on == EndsWhenOn::Event
+            && self
+                .ends_when_for(event.as_log())
+                .map(|c| c.check(&event))
+                .unwrap_or(false);
 
         let event = event.into_log();
-        let discriminant = Discriminant::from_log_event(&event, &self.group_by);
+        let discriminant = self.discriminant(&event);
+
+        if let Some(field) = &self.ends_when_field_changes {
+            let changed = self
+                .reduce_merge_states
+                .get(&discriminant)
+                .map_or(false, |state| {
+                    state.tracked_value.as_ref() != event.get(field)
+                });
+            if changed {
+                if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
+                    output.push(
+                        state
+                            .flush(
+                                self.reason_key.as_deref(),
+                                &self.drop_fields,
+                                FlushReason::FieldChanged,
+                            )
+                            .into(),
+                    );
+                }
+            }
+        }
 
         if starts_here {
             if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
-                output.push(state.flush().into());
+                output.push(
+                    state
+                        .flush(
+                            self.reason_key.as_deref(),
+                            &self.drop_fields,
+                            FlushReason::StartsWhen,
+                        )
+                        .into(),
+                );
             }
 
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(output, event, discriminant)
         } else if ends_here {
             output.push(match self.reduce_merge_states.remove(&discriminant) {
                 Some(mut state) => {
-                    state.add_event(event, &self.merge_strategies);
-                    state.flush().into()
+                    state.add_event(
+                        event,
+                        &self.merge_strategies,
+                        self.default_merge_strategy.as_ref(),
+                    );
+                    state
+                        .flush(
+                            self.reason_key.as_deref(),
+                            &self.drop_fields,
+                            FlushReason::EndsWhen,
+                        )
+                        .into()
                 }
-                None => ReduceState::new(event, &self.merge_strategies)
-                    .flush()
-                    .into(),
+                None => ReduceState::new(
+                    event,
+                    &self.merge_strategies,
+                    self.default_merge_strategy.as_ref(),
+                )
+                .flush(
+                    self.reason_key.as_deref(),
+                    &self.drop_fields,
+                    FlushReason::EndsWhen,
+                )
+                .into(),
             })
+        } else if self.ends_when_on == EndsWhenOn::State {
+            self.push_or_new_reduce_state(output, event, discriminant.clone());
+            self.flush_if_ended(output, &discriminant);
         } else {
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(output, event, discriminant)
         }
+    }
 
-        self.flush_into(output);
+    /// The condition that determines whether `event` ends its reduce, which
+    /// is `ends_when` or the one selected from `ends_when_by`.
+    fn ends_when_for(&self, event: &LogEvent) -> Option<&dyn Condition> {
+        match &self.ends_when {
+            Some(ends_when) => Some(ends_when.as_ref()),
+            None => self
+                .ends_when_by_field
+                .as_ref()
+                .and_then(|field| event.get(field))
+                .and_then(|value| self.ends_when_by.get(value.to_string_lossy().as_str()))
+                .map(|ends_when| ends_when.as_ref()),
+        }
+    }
+
+    /// Flushes the reduce for `discriminant` if its end condition matches
+    /// its merged state.
+    fn flush_if_ended(&mut self, output: &mut Vec<Event>, discriminant: &Discriminant) {
+        let ended = match self.reduce_merge_states.get(discriminant) {
+            Some(state) if self.ends_when.is_some() || !self.ends_when_by.is_empty() => {
+                let merged = state.merged_event();
+                self.ends_when_for(&merged)
+                    .map_or(false, |ends_when| ends_when.check(&merged.into()))
+            }
+            _ => false,
+        };
+        if ended {
+            if let Some(state) = self.reduce_merge_states.remove(discriminant) {
+                output.push(
+                    state
+                        .flush(
+                            self.reason_key.as_deref(),
+                            &self.drop_fields,
+                            FlushReason::EndsWhen,
+                        )
+                        .into(),
+                );
+            }
+        }
     }
 }
 
@@ -266,6 +730,7 @@ impl TaskTransform for Reduce {
                 let mut output = Vec::new();
                 let done = tokio::select! {
                     _ = flush_stream.tick() => {
+                      emit!(ReduceFlushTicked);
                       me.flush_into(&mut output);
                       false
                     }
@@ -296,9 +761,10 @@ mod test {
     use super::*;
     use crate::{
         config::TransformConfig,
-        event::{LogEvent, Value},
+        event::{LogEvent, MetricValue},
     };
     use serde_json::json;
+    use std::task::Poll;
 
     #[test]
     fn generate_config() {
@@ -419,6 +885,630 @@ merge_strategies.baz = "max"
         assert_eq!(output_1.metadata(), &metadata);
     }
 
+    #[test]
+    fn prefix_merge_strategies() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+merge_strategies."metrics.*" = "sum"
+merge_strategies."metrics.peak" = "max"
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("metrics.requests", 1);
+        e_1.insert("metrics.bytes", 100);
+        e_1.insert("metrics.peak", 7);
+        e_1.insert("other", 1);
+        e_1.insert("request_id", "1");
+        reduce.transform_one(&mut output, e_1.into());
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("metrics.requests", 2);
+        e_2.insert("metrics.bytes", 50);
+        e_2.insert("metrics.peak", 3);
+        e_2.insert("other", 2);
+        e_2.insert("request_id", "1");
+        e_2.insert("test_end", "yep");
+        reduce.transform_one(&mut output, e_2.into());
+
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["metrics.requests"], Value::from(3));
+        assert_eq!(output_1["metrics.bytes"], Value::from(150));
+        // The exact match takes precedence over the prefix.
+        assert_eq!(output_1["metrics.peak"], Value::from(7));
+        assert_eq!(output_1["other"], Value::from(3));
+        assert_eq!(output_1["message"], "test message 1".into());
+    }
+
+    #[tokio::test]
+    async fn default_merge_strategy() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+default_merge_strategy = "array"
+merge_strategies.counter = "sum"
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        e_1.insert("host", "a");
+        e_1.insert("request_id", "1");
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        e_2.insert("host", "b");
+        e_2.insert("request_id", "1");
+        e_2.insert("test_end", "yep");
+
+        let inputs = vec![e_1.into(), e_2.into()];
+        let in_stream = Box::pin(stream::iter(inputs));
+        let mut out_stream = reduce.transform(in_stream);
+
+        let output_1 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(
+            output_1["message"],
+            Value::Array(vec!["test message 1".into(), "test message 2".into()]),
+        );
+        assert_eq!(output_1["host"], Value::Array(vec!["a".into(), "b".into()]),);
+        assert_eq!(output_1["test_end"], Value::Array(vec!["yep".into()]));
+        assert_eq!(output_1["counter"], Value::from(3));
+        assert_eq!(
+            output_1["request_id"],
+            Value::Array(vec!["1".into(), "1".into()])
+        );
+    }
+
+    #[tokio::test]
+    async fn reduce_drop_strategy() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+merge_strategies.nonce = "drop"
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("nonce", "abc");
+        e_1.insert("request_id", "1");
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("nonce", "def");
+        e_2.insert("request_id", "1");
+        e_2.insert("test_end", "yep");
+
+        let inputs = vec![e_1.into(), e_2.into()];
+        let in_stream = Box::pin(stream::iter(inputs));
+        let mut out_stream = reduce.transform(in_stream);
+
+        let output_1 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_1["message"], "test message 1".into());
+        assert!(!output_1.contains("nonce"));
+    }
+
+    #[tokio::test]
+    async fn reduce_counts_flush_ticks() {
+        let _ = crate::metrics::init();
+
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+flush_period_ms = 10
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let (tx, rx) = futures::channel::mpsc::channel::<Event>(1);
+        let mut out_stream = reduce.transform(Box::pin(rx));
+
+        fn ticks(controller: &crate::metrics::Controller) -> f64 {
+            crate::metrics::capture_metrics(controller)
+                .map(Event::into_metric)
+                .find(|metric| metric.name() == "reduce_flush_ticks_total")
+                .map(|metric| match metric.value() {
+                    MetricValue::Counter { value } => *value,
+                    _ => panic!("wrong type"),
+                })
+                .unwrap_or(0.0)
+        }
+        let controller = crate::metrics::get_controller().unwrap();
+        let before = ticks(controller);
+
+        tokio::time::pause();
+
+        // Nothing is ever sent, so the output stream is only driven by the
+        // flush interval, whose first tick is immediately ready.
+        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        for _ in 0..5 {
+            tokio::time::advance(Duration::from_millis(10)).await;
+            assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        }
+        drop(tx);
+
+        // The counter is shared with any other reduce running in this process,
+        // so it can only be checked for at least the ticks driven here.
+        assert!(ticks(controller) - before >= 6.0);
+    }
+
+    #[tokio::test]
+    async fn starts_when_without_start_expires() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+expire_after_ms = 50
+flush_period_ms = 10
+
+[starts_when]
+  type = "check_fields"
+  "test_start.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let (mut tx, rx) = futures::channel::mpsc::channel::<Event>(2);
+        let mut out_stream = reduce.transform(Box::pin(rx));
+
+        for counter in 1..=2 {
+            let mut event = LogEvent::from(format!("test message {}", counter));
+            event.insert("counter", counter);
+            tx.try_send(event.into()).unwrap();
+        }
+
+        // No start event ever arrives, so only expiry can flush the reduce.
+        let output = tokio::time::timeout(Duration::from_secs(1), out_stream.next())
+            .await
+            .expect("reduce was not flushed")
+            .unwrap()
+            .into_log();
+        assert_eq!(output["message"], "test message 1".into());
+        assert_eq!(output["counter"], Value::from(3));
+
+        drop(tx);
+        assert!(out_stream.next().await.is_none());
+    }
+
+    #[test]
+    fn expired_reduce_is_flushed_before_new_event() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+expire_after_ms = 10
+
+[starts_when]
+  type = "check_fields"
+  "test_start.exists" = true
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+
+        let mut output = Vec::new();
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        reduce.transform_one(&mut output, e_1.into());
+        assert!(output.is_empty());
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        reduce.transform_one(&mut output, e_2.into());
+
+        // The first reduce expired, so the second event starts a new one.
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["message"], "test message 1".into());
+        assert_eq!(output_1["counter"], Value::from(1));
+
+        reduce.flush_all_into(&mut output);
+        let output_2 = output.remove(0).into_log();
+        assert_eq!(output_2["message"], "test message 2".into());
+        assert_eq!(output_2["counter"], Value::from(2));
+    }
+
+    #[test]
+    fn reason_key() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+expire_after_ms = 10
+reason_key = "reduce.reason"
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("request_id", "1");
+        reduce.transform_one(&mut output, e_1.into());
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("request_id", "2");
+        reduce.transform_one(&mut output, e_2.into());
+
+        let mut e_3 = LogEvent::from("test message 3");
+        e_3.insert("request_id", "1");
+        e_3.insert("test_end", "yep");
+        reduce.transform_one(&mut output, e_3.into());
+
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["message"], "test message 1".into());
+        assert_eq!(output_1["reduce.reason"], "ends_when".into());
+
+        std::thread::sleep(Duration::from_millis(20));
+        reduce.flush_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        let output_2 = output.remove(0).into_log();
+        assert_eq!(output_2["message"], "test message 2".into());
+        assert_eq!(output_2["reduce.reason"], "expired".into());
+    }
+
+    #[test]
+    fn window_flushes_active_reduce() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+expire_after_ms = 10000
+window_ms = 100
+reason_key = "reason"
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        // Keep the reduce active for longer than the window, so that it
+        // never expires.
+        for counter in 1..=3 {
+            let mut event = LogEvent::from("test message");
+            event.insert("counter", counter);
+            event.insert("request_id", "1");
+            reduce.transform_one(&mut output, event.into());
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(output.is_empty());
+
+        std::thread::sleep(Duration::from_millis(60));
+        reduce.flush_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["counter"], Value::from(6));
+        assert_eq!(output_1["reason"], "window".into());
+
+        // The next event starts a new window.
+        let mut event = LogEvent::from("test message");
+        event.insert("counter", 4);
+        event.insert("request_id", "1");
+        reduce.transform_one(&mut output, event.into());
+        reduce.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn max_lifetime_ms_alias() {
+        let config = toml::from_str::<ReduceConfig>("max_lifetime_ms = 100").unwrap();
+        assert_eq!(config.window_ms, Some(100));
+    }
+
+    #[test]
+    fn arg_max_merge_strategy() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+[merge_strategies]
+  error = { strategy = "arg_max", by = "latency" }
+  latency = "max"
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        for (error, latency) in &[("timeout", 120), ("refused", 480), ("reset", 250)] {
+            let mut event = LogEvent::from("test message");
+            event.insert("request_id", "1");
+            event.insert("error", *error);
+            event.insert("latency", *latency);
+            reduce.transform_one(&mut output, event.into());
+        }
+        reduce.flush_all_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["error"], "refused".into());
+        assert_eq!(output_1["latency"], Value::from(480));
+    }
+
+    #[test]
+    fn ends_when_by_field_value() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+ends_when_by_field = "status"
+
+[ends_when_by]
+  error = "exists(.retries_exhausted)"
+  ok = "exists(.done)"
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        let event = |request_id: &str, status: &str, field: Option<&str>| {
+            let mut event = LogEvent::from("test message");
+            event.insert("request_id", request_id);
+            event.insert("status", status);
+            event.insert("counter", 1);
+            if let Some(field) = field {
+                event.insert(field, true);
+            }
+            Event::from(event)
+        };
+
+        // Each group only ends on the condition selected by its status.
+        reduce.transform_one(&mut output, event("1", "error", None));
+        reduce.transform_one(&mut output, event("2", "ok", None));
+        reduce.transform_one(&mut output, event("1", "error", Some("done")));
+        reduce.transform_one(&mut output, event("2", "ok", Some("retries_exhausted")));
+        assert!(output.is_empty());
+
+        reduce.transform_one(&mut output, event("2", "ok", Some("done")));
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["request_id"], "2".into());
+        assert_eq!(output_1["counter"], Value::from(3));
+
+        reduce.transform_one(&mut output, event("1", "error", Some("retries_exhausted")));
+        assert_eq!(output.len(), 1);
+        let output_2 = output.remove(0).into_log();
+        assert_eq!(output_2["request_id"], "1".into());
+        assert_eq!(output_2["counter"], Value::from(3));
+
+        // Statuses without a condition never end a group early.
+        reduce.transform_one(&mut output, event("3", "pending", Some("done")));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn ends_when_on_state() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+ends_when_on = "state"
+
+[ends_when]
+  type = "vrl"
+  source = "(to_int(.bytes) ?? 0) > 100"
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        for bytes in &[40, 50, 30, 20] {
+            let mut event = LogEvent::from("test message");
+            event.insert("bytes", *bytes);
+            event.insert("request_id", "1");
+            reduce.transform_one(&mut output, event.into());
+        }
+
+        // The third event takes the total past the threshold, although no
+        // single event exceeds it.
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["bytes"], Value::from(120));
+
+        reduce.flush_all_into(&mut output);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output.remove(0).into_log()["bytes"], Value::from(20));
+    }
+
+    #[test]
+    fn ends_when_field_changes() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+ends_when_field_changes = "status"
+reason_key = "reason"
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        for (counter, status) in &[(1, "running"), (2, "running"), (3, "done"), (4, "done")] {
+            let mut event = LogEvent::from("test message");
+            event.insert("counter", *counter);
+            event.insert("status", *status);
+            event.insert("request_id", "1");
+            reduce.transform_one(&mut output, event.into());
+        }
+
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["counter"], Value::from(3));
+        assert_eq!(output_1["status"], "running".into());
+        assert_eq!(output_1["reason"], "field_changed".into());
+
+        reduce.flush_all_into(&mut output);
+        assert_eq!(output.len(), 1);
+        let output_2 = output.remove(0).into_log();
+        assert_eq!(output_2["counter"], Value::from(7));
+        assert_eq!(output_2["status"], "done".into());
+    }
+
+    #[test]
+    fn drop_group_by_fields() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+drop_group_by_fields = true
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        e_1.insert("request_id", "1");
+        reduce.transform_one(&mut output, e_1.into());
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        e_2.insert("request_id", "2");
+        reduce.transform_one(&mut output, e_2.into());
+
+        let mut e_3 = LogEvent::from("test message 3");
+        e_3.insert("counter", 3);
+        e_3.insert("request_id", "1");
+        e_3.insert("test_end", "yep");
+        reduce.transform_one(&mut output, e_3.into());
+
+        assert_eq!(output.len(), 1);
+        let output_1 = output.remove(0).into_log();
+        assert_eq!(output_1["message"], "test message 1".into());
+        assert_eq!(output_1["counter"], Value::from(4));
+        assert!(!output_1.contains("request_id"));
+
+        reduce.flush_all_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        let output_2 = output.remove(0).into_log();
+        assert_eq!(output_2["message"], "test message 2".into());
+        assert!(!output_2.contains("request_id"));
+    }
+
+    #[test]
+    fn passthrough_originals() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+passthrough_originals = true
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap();
+        let mut reduce = Reduce::new(&config).unwrap();
+        let mut output = Vec::new();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        reduce.transform_one(&mut output, e_1.into());
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        e_2.insert("test_end", "yep");
+        reduce.transform_one(&mut output, e_2.into());
+
+        assert_eq!(output.len(), 3);
+        let original_1 = output.remove(0).into_log();
+        assert_eq!(original_1["message"], "test message 1".into());
+        assert_eq!(original_1["counter"], Value::from(1));
+        assert_eq!(original_1[ORIGINAL_MARKER_KEY], Value::from(true));
+
+        let original_2 = output.remove(0).into_log();
+        assert_eq!(original_2["message"], "test message 2".into());
+        assert_eq!(original_2["counter"], Value::from(2));
+        assert_eq!(original_2[ORIGINAL_MARKER_KEY], Value::from(true));
+
+        let reduced = output.remove(0).into_log();
+        assert_eq!(reduced["message"], "test message 1".into());
+        assert_eq!(reduced["counter"], Value::from(3));
+        assert!(!reduced.contains(ORIGINAL_MARKER_KEY));
+    }
+
+    #[test]
+    fn fields_keep_first_seen_order() {
+        let strategies = IndexMap::new();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("zebra", 1);
+        e_1.insert("apple", 1);
+        let mut state = ReduceState::new(e_1, &strategies, None);
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("mango", 2);
+        e_2.insert("apple", 2);
+        e_2.insert("banana", 2);
+        state.add_event(e_2, &strategies, None);
+
+        let mut e_3 = LogEvent::from("test message 3");
+        e_3.insert("cherry", 3);
+        state.add_event(e_3, &strategies, None);
+
+        // Each event's fields arrive sorted, new fields are appended after
+        // those of earlier events.
+        let keys = state.fields.keys().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                "apple",
+                "message",
+                "timestamp",
+                "zebra",
+                "banana",
+                "mango",
+                "cherry"
+            ]
+        );
+
+        let output = state.flush(None, &[], FlushReason::Shutdown);
+        assert_eq!(output["apple"], Value::from(3));
+        assert_eq!(output["cherry"], Value::from(3));
+    }
+
     #[tokio::test]
     async fn missing_group_by() {
         let reduce = toml::from_str::<ReduceConfig>(
@@ -550,4 +1640,187 @@ merge_strategies.bar = "concat"
         assert_eq!(output_2["bar"], json!([2, 4, 6, 8, "done"]).into());
         assert_eq!(output_2.metadata(), &metadata_2);
     }
+
+    #[tokio::test]
+    async fn max_bytes_per_group() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+max_bytes_per_group = 2000
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        e_1.insert("request_id", "1");
+
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        e_2.insert("request_id", "1");
+        e_2.insert("payload", "x".repeat(4000));
+
+        let mut e_3 = LogEvent::from("test message 3");
+        e_3.insert("counter", 3);
+        e_3.insert("request_id", "1");
+        e_3.insert("test_end", "yep");
+
+        let inputs = vec![e_1.into(), e_2.into(), e_3.into()];
+        let in_stream = Box::pin(stream::iter(inputs));
+        let mut out_stream = reduce.transform(in_stream);
+
+        // The oversized payload flushes the group before the end condition.
+        let output_1 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_1["message"], "test message 1".into());
+        assert_eq!(output_1["counter"], Value::from(3));
+        assert!(output_1.contains("payload"));
+
+        let output_2 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_2["message"], "test message 3".into());
+        assert_eq!(output_2["counter"], Value::from(3));
+        assert!(!output_2.contains("payload"));
+
+        assert!(out_stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn group_by_template() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by_template = "{{ region }}-{{ service }}"
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let events = vec![
+            ("eu", "api"),
+            ("eu", "web"),
+            ("us", "api"),
+            ("eu", "api"),
+            ("eu", "web"),
+        ]
+        .into_iter()
+        .map(|(region, service)| {
+            let mut event = LogEvent::from("test message");
+            event.insert("region", region);
+            event.insert("service", service);
+            event.insert("counter", 1);
+            event.into()
+        })
+        .collect::<Vec<Event>>();
+
+        let mut outputs = reduce
+            .transform(Box::pin(stream::iter(events)))
+            .map(Event::into_log)
+            .collect::<Vec<_>>()
+            .await;
+        outputs.sort_by_key(|log| {
+            (
+                log["region"].to_string_lossy(),
+                log["service"].to_string_lossy(),
+            )
+        });
+
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(outputs[0]["region"], "eu".into());
+        assert_eq!(outputs[0]["service"], "api".into());
+        assert_eq!(outputs[0]["counter"], Value::from(2));
+        assert_eq!(outputs[1]["region"], "eu".into());
+        assert_eq!(outputs[1]["service"], "web".into());
+        assert_eq!(outputs[1]["counter"], Value::from(2));
+        assert_eq!(outputs[2]["region"], "us".into());
+        assert_eq!(outputs[2]["service"], "api".into());
+        assert_eq!(outputs[2]["counter"], Value::from(1));
+    }
+
+    #[test]
+    fn group_by_and_group_by_template_are_exclusive() {
+        let config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "region" ]
+group_by_template = "{{ region }}-{{ service }}"
+"#,
+        )
+        .unwrap();
+
+        assert!(Reduce::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn distinct_group_by_values() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "a", "b" ]
+"#,
+        )
+        .unwrap()
+        .build(&GlobalOptions::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        // Each pair of fields concatenates to the same string, but must still
+        // be reduced separately.
+        let events = vec![("x", "yz"), ("xy", "z"), ("x", "yz"), ("xy", "z")]
+            .into_iter()
+            .map(|(a, b)| {
+                let mut event = LogEvent::from("test message");
+                event.insert("a", a);
+                event.insert("b", b);
+                event.insert("counter", 1);
+                event.into()
+            })
+            .collect::<Vec<Event>>();
+
+        let mut outputs = reduce
+            .transform(Box::pin(stream::iter(events)))
+            .map(Event::into_log)
+            .collect::<Vec<_>>()
+            .await;
+        outputs.sort_by_key(|log| log["a"].to_string_lossy());
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0]["a"], "x".into());
+        assert_eq!(outputs[0]["b"], "yz".into());
+        assert_eq!(outputs[0]["counter"], Value::from(2));
+        assert_eq!(outputs[1]["a"], "xy".into());
+        assert_eq!(outputs[1]["b"], "z".into());
+        assert_eq!(outputs[1]["counter"], Value::from(2));
+    }
+
+    #[test]
+    fn metric_logs_grouped_by_namespace() {
+        // Metrics converted by `metric_to_log` have their namespace in a
+        // field of its own, which can be grouped by like any other.
+        let groups = |config: &str| {
+            let config = toml::from_str::<ReduceConfig>(config).unwrap();
+            let mut reduce = Reduce::new(&config).unwrap();
+            let mut output = Vec::new();
+            for namespace in &["app", "system", "app"] {
+                let mut event = LogEvent::default();
+                event.insert("name", "requests");
+                event.insert("namespace", *namespace);
+                event.insert("kind", "incremental");
+                event.insert("counter.value", 1.0);
+                reduce.transform_one(&mut output, event.into());
+            }
+            reduce.flush_all_into(&mut output);
+            output.len()
+        };
+
+        assert_eq!(groups(r#"group_by = [ "name", "namespace" ]"#), 2);
+        assert_eq!(groups(r#"group_by = [ "name" ]"#), 1);
+    }
 }